@@ -0,0 +1,109 @@
+//! Minimal Metaplex Bubblegum support for compressed NFTs. A compressed NFT
+//! is a leaf in a concurrent Merkle tree rather than a token account, and
+//! moves through Bubblegum's `transfer`, built by hand here so the program
+//! doesn't pull in the mpl crates.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+
+use crate::errors::AuctionError;
+
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+// sha256("global:transfer")[..8]
+const TRANSFER: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+/// Remaining accounts ahead of the proof, see `LeafTransfer::new`.
+pub const COMPRESSED_ACCOUNTS: usize = 5;
+
+/// The asset id indexers know a compressed NFT by.
+pub fn asset_id(tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// A leaf as Bubblegum hashes it, less the owner and delegate.
+pub struct Leaf {
+    pub root: [u8; 32], // tree root the proof was taken against
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub leaf_index: u32, // Bubblegum mints nonces in step with leaf indexes
+}
+
+/// The Bubblegum and tree accounts around a compressed NFT transfer.
+pub struct LeafTransfer<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+    proof: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> LeafTransfer<'a, 'info> {
+    /// `accounts` holds, in order: the Bubblegum program, the tree's config
+    /// PDA, the tree, the noop log wrapper and the account compression
+    /// program, followed by `proof_len` proof nodes. Bubblegum checks the
+    /// config PDA, the programs it calls and the proof itself.
+    pub fn new(accounts: &'a [AccountInfo<'info>], tree: &Pubkey, proof_len: usize) -> Result<Self> {
+        require!(
+            accounts.len() >= COMPRESSED_ACCOUNTS + proof_len
+                && accounts[0].key() == BUBBLEGUM_PROGRAM_ID
+                && accounts[2].key() == *tree,
+            AuctionError::CompressedAccountsRequired
+        );
+        let (accounts, rest) = accounts.split_at(COMPRESSED_ACCOUNTS);
+        Ok(Self { accounts, proof: &rest[..proof_len] })
+    }
+
+    /// Moves the leaf from `owner`, who also holds its delegation, to `new_owner`.
+    pub fn transfer(
+        &self,
+        owner: AccountInfo<'info>,
+        new_owner: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        leaf: &Leaf,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let [program, tree_config, tree, log_wrapper, compression_program] = self.accounts else {
+            return err!(AuctionError::CompressedAccountsRequired);
+        };
+
+        let mut data = TRANSFER.to_vec();
+        data.extend_from_slice(&leaf.root);
+        data.extend_from_slice(&leaf.data_hash);
+        data.extend_from_slice(&leaf.creator_hash);
+        data.extend_from_slice(&u64::from(leaf.leaf_index).to_le_bytes());
+        data.extend_from_slice(&leaf.leaf_index.to_le_bytes());
+
+        let mut metas = vec![
+            AccountMeta::new_readonly(tree_config.key(), false),
+            AccountMeta::new_readonly(owner.key(), true),
+            AccountMeta::new_readonly(owner.key(), false), // leaf delegate
+            AccountMeta::new_readonly(new_owner.key(), false),
+            AccountMeta::new(tree.key(), false),
+            AccountMeta::new_readonly(log_wrapper.key(), false),
+            AccountMeta::new_readonly(compression_program.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ];
+        metas.extend(self.proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+        let mut infos = vec![
+            tree_config.clone(),
+            owner,
+            new_owner,
+            tree.clone(),
+            log_wrapper.clone(),
+            compression_program.clone(),
+            system_program,
+            program.clone(),
+        ];
+        infos.extend_from_slice(self.proof);
+
+        invoke_signed(
+            &Instruction { program_id: BUBBLEGUM_PROGRAM_ID, accounts: metas, data },
+            &infos,
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+}
@@ -29,4 +29,145 @@ pub enum AuctionError {
 
     #[msg("Bid not active")]
     BidNotActive,
+
+    #[msg("Soft close window and extension must be positive")]
+    InvalidSoftClose,
+
+    #[msg("Dutch start price must exceed floor price, and floor must cover the reserve")]
+    InvalidDutchPricing,
+
+    #[msg("Dutch price step interval and amount must be positive")]
+    InvalidPriceDecay,
+
+    #[msg("Reveal phase must end after the auction, and penalty cannot exceed 100%")]
+    InvalidSealedBidConfig,
+
+    #[msg("Auction is not in its reveal phase")]
+    NotRevealing,
+
+    #[msg("Winner token account required to refund the price difference")]
+    WinnerAccountRequired,
+
+    #[msg("Buy-it-now is only for English auctions and must cover the reserve")]
+    InvalidBuyNowPrice,
+
+    #[msg("Bid increment must be positive and tiers ascending")]
+    InvalidBidIncrement,
+
+    #[msg("Multi-unit auctions must be English, without buy-it-now, and hold the units on sale")]
+    InvalidMultiUnit,
+
+    #[msg("Unit book required for multi-unit auctions")]
+    UnitBookRequired,
+
+    #[msg("Lot items must be distinct auction-owned vaults holding their mint, up to the lot limit")]
+    InvalidLot,
+
+    #[msg("Lot manifest and every lot vault required to settle a lot")]
+    LotAccountsRequired,
+
+    #[msg("Hidden reserves need a zero public reserve, no buy-it-now or Dutch pricing, and a deadline after bidding closes")]
+    InvalidHiddenReserve,
+
+    #[msg("Auction has no reserve left to reveal")]
+    NoHiddenReserve,
+
+    #[msg("Revealed reserve does not match the commitment")]
+    ReserveCommitmentMismatch,
+
+    #[msg("Reserve reveal deadline has passed")]
+    ReserveRevealExpired,
+
+    #[msg("Hidden reserve must be revealed before settlement")]
+    ReserveNotRevealed,
+
+    #[msg("Auction can no longer be cancelled")]
+    CannotCancel,
+
+    #[msg("Auction already has bids and no cancellation fee applies to them")]
+    BidsExist,
+
+    #[msg("Seller token account, treasury and payment mint required to pay the cancellation fee")]
+    CancellationFeeAccountsRequired,
+
+    #[msg("Only the seller or platform admin can pause or resume an auction")]
+    NotSellerOrAdmin,
+
+    #[msg("Only a running auction can be paused")]
+    CannotPause,
+
+    #[msg("Auction is not paused")]
+    NotPaused,
+
+    #[msg("Only a pending auction can be activated")]
+    NotPending,
+
+    #[msg("Auction start date has not passed")]
+    NotStarted,
+
+    #[msg("Auction duration is outside the platform's limits")]
+    InvalidDuration,
+
+    #[msg("Auction can no longer be amended")]
+    CannotAmend,
+
+    #[msg("Amendment would worsen terms for existing bidders")]
+    AmendmentDisfavoursBidders,
+
+    #[msg("New end date is in the past")]
+    EndDateInPast,
+
+    #[msg("Participation bonds must be positive and are only available on PhysicalRWA auctions")]
+    InvalidParticipationBond,
+
+    #[msg("Native SOL auctions support open English or Dutch bidding only")]
+    NativeSolUnsupported,
+
+    #[msg("Payment options need distinct mints with positive rates on a single-item English SPL-token auction")]
+    InvalidPaymentOptions,
+
+    #[msg("USD pricing needs a single-item English auction without hidden reserve, buy-now or extra payment mints, and sane oracle bounds")]
+    InvalidUsdPricing,
+
+    #[msg("A Pyth price update is required to settle a USD-priced auction")]
+    PriceUpdateRequired,
+
+    #[msg("Price update isn't a verified Pyth update for this auction's feed")]
+    InvalidPriceUpdate,
+
+    #[msg("Oracle price is too old")]
+    StalePrice,
+
+    #[msg("Oracle confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Mint carries a Token-2022 extension the auction can't support")]
+    UnsupportedMintExtension,
+
+    #[msg("Transfer fee calculation overflowed")]
+    TransferFeeOverflow,
+
+    #[msg("Programmable NFT transfer accounts are missing or wrong")]
+    ProgrammableAccountsRequired,
+
+    #[msg("Metadata account does not belong to the item mint")]
+    InvalidItemMetadata,
+
+    #[msg("Item vault is frozen; programmable NFTs need their metadata account")]
+    ItemVaultFrozen,
+
+    #[msg("Item mint and vault accounts are required")]
+    ItemAccountsRequired,
+
+    #[msg("Compressed NFT transfer accounts or proof are missing or wrong")]
+    CompressedAccountsRequired,
+
+    #[msg("Compressed items are sold alone, without a mint, vault or lot")]
+    InvalidCompressedItem,
+
+    #[msg("No unsold units to reclaim yet")]
+    NoUnsoldUnits,
+
+    #[msg("The auction's reserve is met")]
+    ReserveMet,
 }
\ No newline at end of file
@@ -21,4 +21,6 @@ pub enum AuctionAuthError {
     InvalidAuthentication,
     #[msg("No authenticator available")]
     NoAuthenticatorAvailable,
+    #[msg("Item has not been verified")]
+    NotVerified,
 }
\ No newline at end of file
@@ -5,9 +5,15 @@ pub enum BidError {
     #[msg("Auction not available")]
     AuctionNotAvailable,
 
+    #[msg("Auction is paused")]
+    AuctionPaused,
+
     #[msg("The Auction is Closed for Bidding")]
     BiddingClosed,
 
+    #[msg("Auction has not started")]
+    NotStarted,
+
     #[msg("Amount is less than current highest bid")]
     BidTooLow,
 
@@ -16,5 +22,86 @@ pub enum BidError {
 
     #[msg("Withdrawal disabled. You are currently winnning")]
     StillWinning,
+
+    #[msg("Amount is less than the current Dutch price")]
+    BelowDutchPrice,
+
+    #[msg("Sealed-bid auctions only accept commitments")]
+    SealedBidOnly,
+
+    #[msg("Auction does not accept sealed bids")]
+    NotSealedBid,
+
+    #[msg("Revealed bid does not match commitment")]
+    CommitmentMismatch,
+
+    #[msg("Revealed bid exceeds escrowed deposit")]
+    DepositTooLow,
+
+    #[msg("Bid already revealed")]
+    AlreadyRevealed,
+
+    #[msg("Seller token account required to collect the penalty")]
+    SellerAccountRequired,
+
+    #[msg("Buy-it-now is not available for this auction")]
+    BuyNowUnavailable,
+
+    #[msg("Bid is below the minimum acceptable next bid")]
+    BelowMinimumBid,
+
+    #[msg("Multi-unit auctions only accept unit bids")]
+    MultiUnitOnly,
+
+    #[msg("Auction does not sell multiple units")]
+    NotMultiUnit,
+
+    #[msg("Quantity must be between 1 and the units on sale")]
+    InvalidQuantity,
+
+    #[msg("Proxy bidding is only available on single-item English auctions")]
+    ProxyBidUnavailable,
+
+    #[msg("Bidder is not on this auction's allowlist")]
+    NotAllowlisted,
+
+    #[msg("Holder token account (and metadata for collection gates) required")]
+    GateAccountsRequired,
+
+    #[msg("Bidder does not hold the token this auction is gated on")]
+    NotTokenHolder,
+
+    #[msg("Metadata account does not belong to the held token")]
+    InvalidGateMetadata,
+
+    #[msg("Auction requires a participation bond before bidding")]
+    BondRequired,
+
+    #[msg("Auction does not take participation bonds")]
+    NoParticipationBond,
+
+    #[msg("Bond stays locked until the auction is over and the bid is withdrawn or settled")]
+    BondLocked,
+
+    #[msg("Bond vault account required to release the bond")]
+    BondVaultRequired,
+
+    #[msg("Bond is only forfeitable over an open bid, or from a winner who never settled")]
+    BondNotForfeitable,
+
+    #[msg("Token mint and token accounts are required for SPL-token auctions")]
+    TokenAccountsRequired,
+
+    #[msg("Bid amount overflows once converted")]
+    ConversionOverflow,
+
+    #[msg("An existing bid can only be raised in the mint it was placed in")]
+    PaymentMintChanged,
+
+    #[msg("Bid amount overflows")]
+    AmountOverflow,
+
+    #[msg("Unit book already holds the most winning bids it can")]
+    UnitBookFull,
 }
 
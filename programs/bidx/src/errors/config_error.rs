@@ -15,5 +15,9 @@ pub enum ConfigError {
     #[msg("Admins can't be authenticators")]
     AdminCannotbeAuthenticator,
     #[msg("Authenticator not in registory")]
-    AuthenticatorNotInRegistry
+    AuthenticatorNotInRegistry,
+    #[msg("Platform is paused")]
+    PlatformPaused,
+    #[msg("Platform config is not the one the auction was created under")]
+    WrongPlatformConfig,
 }
\ No newline at end of file
@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 
-use crate::states::AssetType;
+use crate::states::{AssetType, ForfeitReason};
 
 #[event]
 pub struct AuctionSettled {
     pub auction: Pubkey,
     pub winner: Pubkey,
-    pub final_price: u64,
+    pub winning_bid: u64,
+    pub clearing_price: u64,
+    pub units: u64,
     pub platform_fee: u64,
     pub auth_fee: u64,
     pub seller_amount: u64,
@@ -24,5 +26,137 @@ pub struct AuctionCreated {
 pub struct BidWithdrawn {
     pub auction: Pubkey,
     pub bidder: Pubkey,
-    pub ammount: u64
-}
\ No newline at end of file
+    pub ammount: u64,
+    pub penalty: u64,
+}
+
+#[event]
+pub struct AuctionExtended {
+    pub auction: Pubkey,
+    pub previous_end_date: i64,
+    pub new_end_date: i64,
+    pub extension_count: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidCommitted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidRevealed {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub is_highest: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionBoughtNow {
+    pub auction: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnitBidPlaced {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub unit_price: u64,
+    pub quantity: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProxyBidRaised {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub highest_bid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveRevealed {
+    pub auction: Pubkey,
+    pub reserved_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub cancellation_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionFailed {
+    pub auction: Pubkey,
+    pub highest_bid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionPaused {
+    pub auction: Pubkey,
+    pub paused_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionResumed {
+    pub auction: Pubkey,
+    pub resumed_by: Pubkey,
+    pub paused_for: i64,
+    pub new_end_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionActivated {
+    pub auction: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionAmended {
+    pub auction: Pubkey,
+    pub fields: Vec<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondPosted {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BondReleased {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BondForfeited {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub reason: ForfeitReason,
+}
+
+#[event]
+pub struct UnsoldUnitsReclaimed {
+    pub auction: Pubkey,
+    pub seller: Pubkey,
+    pub units: u64,
+}
@@ -36,4 +36,11 @@ pub struct AuthenticatorRemovedFromPlatform {
 pub struct PlatformPauseToggled {
     pub is_paused: bool,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct TreasuryVaultOpened {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,146 @@
+//! Token-2022 mint extensions the program can work with. Transfer fees are
+//! withheld from what the receiving account gets, so escrow accounting runs
+//! on amounts received rather than amounts sent, and are harvested to the
+//! mint before an escrow closes. Transfer-hook mints need
+//! the hook program, its extra-account-metas PDA and the accounts listed
+//! there on every transfer; callers pass them in as remaining accounts and
+//! `transfer_checked` forwards whatever the mint asks for.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::{harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    onchain::invoke_transfer_checked,
+    state::Mint,
+};
+
+use crate::errors::AuctionError;
+
+// metadata and grouping only describe the token
+const DESCRIPTIVE_EXTENSIONS: [ExtensionType; 6] = [
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+];
+
+/// Auctioned items move one unit at a time, which a transfer fee would swallow.
+pub fn require_supported_item_mint(mint: &AccountInfo) -> Result<()> {
+    require_extensions(
+        mint,
+        &[ExtensionType::MintCloseAuthority, ExtensionType::TransferHook],
+    )
+}
+
+/// Payment mints may charge transfer fees, see `received` and `gross_up`.
+pub fn require_supported_payment_mint(mint: &AccountInfo) -> Result<()> {
+    require_extensions(
+        mint,
+        &[ExtensionType::TransferFeeConfig, ExtensionType::TransferHook],
+    )
+}
+
+fn require_extensions(mint: &AccountInfo, allowed: &[ExtensionType]) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    require!(
+        state
+            .get_extension_types()?
+            .iter()
+            .all(|extension| DESCRIPTIVE_EXTENSIONS.contains(extension) || allowed.contains(extension)),
+        AuctionError::UnsupportedMintExtension
+    );
+    Ok(())
+}
+
+/// What arrives when `amount` of `mint` is sent, net of this epoch's transfer fee.
+pub fn received(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint)? {
+        Some(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(AuctionError::TransferFeeOverflow)?,
+        None => 0,
+    };
+    Ok(amount - fee)
+}
+
+/// What has to be sent for `net` of `mint` to arrive after this epoch's transfer fee.
+pub fn gross_up(mint: &AccountInfo, net: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint)? {
+        Some(config) if net > 0 => config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, net)
+            .ok_or(AuctionError::TransferFeeOverflow)?,
+        _ => 0,
+    };
+    net.checked_add(fee).ok_or(error!(AuctionError::TransferFeeOverflow))
+}
+
+/// Moves the transfer fees withheld on `account` to `mint`, which has to be
+/// writable; Token-2022 won't close an account still holding withheld fees.
+/// Mints without a transfer fee are left alone.
+pub fn harvest_withheld_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+) -> Result<()> {
+    if transfer_fee_config(mint)?.is_none() {
+        return Ok(());
+    }
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            token_program.clone(),
+            HarvestWithheldTokensToMint {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        vec![account.clone()],
+    )
+}
+
+fn transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(state.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// `transfer_checked` that resolves the mint's transfer-hook extra accounts
+/// out of `hook_accounts` and forwards them. Mints without a hook, classic
+/// SPL ones included, ignore `hook_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{AuctionAuthError, AuctionError};
+use crate::events::AuctionActivated;
+use crate::states::{Auction, AuctionStatus, AuthStatus};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ActivateAuction<'info> {
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+}
+
+impl<'info> ActivateAuction<'info> {
+    pub fn activate_auction(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.auction.auction_status == AuctionStatus::Pending,
+            AuctionError::NotPending
+        );
+        require!(now >= self.auction.start_date, AuctionError::NotStarted);
+
+        // physical items must pass authentication first
+        require!(
+            self.auction.auth_status == AuthStatus::NotRequired
+                || self.auction.auth_status == AuthStatus::Verified,
+            AuctionAuthError::NotVerified
+        );
+
+        self.auction.auction_status = AuctionStatus::Active;
+
+        emit!(
+            AuctionActivated {
+                auction: self.auction.key(),
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::{AuctionError, ConfigError};
+use crate::events::AuctionAmended;
+use crate::states::{Auction, AuctionStatus, AuctionType, PlatformConfig};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct AmendAuction<'info> {
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+impl<'info> AmendAuction<'info> {
+    /// `buy_now_price` of `Some(0)` removes buy-it-now. Values equal to the
+    /// current terms are left out of the amendment.
+    pub fn amend_auction(
+        &mut self,
+        starting_bid: Option<u64>,
+        reserved_price: Option<u64>,
+        end_date: Option<i64>,
+        buy_now_price: Option<u64>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let current = &self.auction;
+
+        require!(
+            matches!(
+                current.auction_status,
+                AuctionStatus::Pending | AuctionStatus::Active | AuctionStatus::Paused
+            ),
+            AuctionError::CannotAmend
+        );
+
+        let has_bids = current.highest_bidder != Pubkey::default() || current.sealed_commitments > 0;
+
+        let mut amended = (**current).clone();
+        let mut updated_fields: Vec<String> = vec![];
+
+        if let Some(starting_bid) = starting_bid.filter(|price| *price != current.starting_bid) {
+            require!(
+                !has_bids || starting_bid <= current.starting_bid,
+                AuctionError::AmendmentDisfavoursBidders
+            );
+            amended.starting_bid = starting_bid;
+            updated_fields.push(String::from("starting_bid"));
+        }
+
+        if let Some(reserved_price) = reserved_price.filter(|price| *price != current.reserved_price) {
+            require!(
+                !has_bids || reserved_price <= current.reserved_price,
+                AuctionError::AmendmentDisfavoursBidders
+            );
+            amended.reserved_price = reserved_price;
+            updated_fields.push(String::from("reserved_price"));
+        }
+
+        if let Some(end_date) = end_date.filter(|date| *date != current.end_date) {
+            // bidders committed funds against the advertised close
+            require!(!has_bids, AuctionError::AmendmentDisfavoursBidders);
+            require!(end_date > now, AuctionError::EndDateInPast);
+            if let AuctionType::SealedBid { reveal_end_date, .. } = &mut amended.auction_type {
+                // keep the reveal phase the same length
+                *reveal_end_date += end_date - current.end_date;
+            }
+            amended.end_date = end_date;
+            updated_fields.push(String::from("end_date"));
+        }
+
+        let buy_now_price = buy_now_price
+            .map(|price| (price > 0).then_some(price))
+            .filter(|price| *price != current.buy_now_price);
+        if let Some(buy_now_price) = buy_now_price {
+            require!(
+                !has_bids
+                    || matches!(
+                        (buy_now_price, current.buy_now_price),
+                        (Some(new_price), Some(old_price)) if new_price <= old_price
+                    ),
+                AuctionError::AmendmentDisfavoursBidders
+            );
+            amended.buy_now_price = buy_now_price;
+            updated_fields.push(String::from("buy_now_price"));
+        }
+
+        amended.validate_terms(&self.platform_config)?;
+
+        // a lower reserve may now be covered by the leader's proxy maximum
+        amended.lift_to_reserve();
+
+        self.auction.set_inner(amended);
+
+        emit!(
+            AuctionAmended {
+                auction: self.auction.key(),
+                fields: updated_fields,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@ pub struct AttestAuthentication<'info> {
     pub seller: UncheckedAccount<'info>,
 
     #[account(
+        mut,
         seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
         bump
     )]
@@ -54,8 +55,8 @@ impl <'info> AttestAuthentication <'info> {
 
         if approved {
             self.authentication.auth_status = AuthStatus::Verified;
+            // activate_auction opens it once start_date has passed
             self.auction.auth_status = AuthStatus::Verified;
-            self.auction.auction_status = AuctionStatus::Active;
         } else {
             self.authentication.auth_status = AuthStatus::Rejected;
             self.auction.auth_status = AuthStatus::Rejected;
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+
+use crate::events::UnitBidPlaced;
+use crate::states::{AuctionStatus, UnitBid, UnitBook, PlatformConfig, auction::Auction, bid::Bid, ParticipationBond};
+use crate::errors::{BidError, ConfigError};
+use crate::extensions;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount, TokenInterface,
+    },
+};
+
+#[derive(Accounts)]
+pub struct BidUnits<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", bidder.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bid: Box<Account<'info, Bid>>,
+
+    #[account(mut)]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    /// Bidder's holding of the gating token, see `Auction::bidder_gate`
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Metaplex metadata of the held token, checked against its PDA for collection gates
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + UnitBook::INIT_SPACE,
+        seeds = [b"unit_book", auction.key().as_ref()],
+        bump
+    )]
+    pub unit_book: Box<Account<'info, UnitBook>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = token_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BidUnits<'info> {
+    pub fn bid_units(
+        &mut self,
+        unit_price: u64,
+        quantity: u64,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &BidUnitsBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Validations
+        require!(
+            self.auction.auction_status != AuctionStatus::Paused,
+            BidError::AuctionPaused
+        );
+        require!(
+            self.auction.auction_status == AuctionStatus::Active,
+            BidError::AuctionNotAvailable
+        );
+        require!(
+            self.token_mint.key() == self.auction.accepted_token,
+            BidError::WrongToken
+        );
+        let units_for_sale = self
+            .auction
+            .multi_unit
+            .as_ref()
+            .map(|multi_unit| multi_unit.quantity)
+            .ok_or(BidError::NotMultiUnit)?;
+        require!(
+            quantity > 0 && quantity <= units_for_sale,
+            BidError::InvalidQuantity
+        );
+        require!(unit_price >= self.auction.starting_bid, BidError::BelowMinimumBid);
+        self.auction.require_allowlisted(&self.bidder.key(), allowlist_proof)?;
+        self.auction.require_gate_holder(
+            &self.bidder.key(),
+            self.gate_token_account.as_deref(),
+            self.gate_metadata.as_deref(),
+        )?;
+        self.auction.require_bond_posted(self.bond.as_deref())?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.auction.require_bidding_open(now)?;
+        let total = unit_price.checked_mul(quantity).ok_or(BidError::InvalidQuantity)?;
+
+        // Existing bidders re-price by topping up their escrow
+        let top_up = if self.bid.bidder != Pubkey::default() {
+            require!(total >= self.bid.amount, BidError::BidTooLow);
+            let top_up = total - self.bid.amount;
+            self.bid.amount = total;
+            self.bid.quantity = quantity;
+            self.bid.time_stamp = now;
+            top_up
+        } else {
+            self.bid.set_inner(Bid {
+                amount: total,
+                quantity,
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                is_active: true,
+                is_winner: false,
+                commitment: None,
+                revealed: false,
+                time_stamp: now,
+                token_mint: self.token_mint.key(),
+                bump: bumps.bid,
+            });
+            total
+        };
+
+        if self.unit_book.auction == Pubkey::default() {
+            self.unit_book.auction = self.auction.key();
+            self.unit_book.bump = bumps.unit_book;
+        }
+
+        // A full book must be beaten on price to win any unit
+        let min_unit_price = if self.unit_book.allocated_units(units_for_sale) >= units_for_sale {
+            self.unit_book.clearing_price() + 1
+        } else {
+            self.auction.starting_bid
+        };
+
+        self.unit_book.insert(
+            UnitBid {
+                bidder: self.bidder.key(),
+                unit_price,
+                quantity,
+            },
+            units_for_sale,
+        )?;
+        if self.unit_book.allocation(&self.bidder.key(), units_for_sale).is_none() {
+            msg!("Minimum acceptable unit price: {}", min_unit_price);
+            return err!(BidError::BelowMinimumBid);
+        }
+
+        // Transfer tokens to escrow, covering any transfer fee so it holds the full top-up
+        if top_up > 0 {
+            extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                self.bidder_token_account.to_account_info(),
+                self.token_mint.to_account_info(),
+                self.escrow_vault.to_account_info(),
+                self.bidder.to_account_info(),
+                hook_accounts,
+                extensions::gross_up(&self.token_mint.to_account_info(), top_up)?,
+                self.token_mint.decimals,
+                &[],
+            )?;
+        }
+
+        // Track the best unit bid on the auction for display
+        if let Some(best) = self.unit_book.entries.first() {
+            self.auction.highest_bid = best.unit_price;
+            self.auction.highest_bidder = best.bidder;
+        }
+
+        emit!(
+            UnitBidPlaced {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                unit_price,
+                quantity,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
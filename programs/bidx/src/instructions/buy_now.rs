@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AuctionBoughtNow;
+use crate::states::{AuctionStatus, PlatformConfig, auction::Auction, bid::Bid, ParticipationBond};
+use crate::errors::{BidError, ConfigError};
+use crate::extensions;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount, TokenInterface,
+    },
+};
+
+#[derive(Accounts)]
+pub struct BuyNow<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", buyer.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Bidder's holding of the gating token, see `Auction::bidder_gate`
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Metaplex metadata of the held token, checked against its PDA for collection gates
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"bond", buyer.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = token_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyNow<'info> {
+    pub fn buy_now(
+        &mut self,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &BuyNowBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Validations
+        require!(
+            self.auction.auction_status != AuctionStatus::Paused,
+            BidError::AuctionPaused
+        );
+        require!(
+            self.auction.auction_status == AuctionStatus::Active,
+            BidError::AuctionNotAvailable
+        );
+        require!(
+            self.token_mint.key() == self.auction.accepted_token,
+            BidError::WrongToken
+        );
+        self.auction.require_allowlisted(&self.buyer.key(), allowlist_proof)?;
+        self.auction.require_gate_holder(
+            &self.buyer.key(),
+            self.gate_token_account.as_deref(),
+            self.gate_metadata.as_deref(),
+        )?;
+        self.auction.require_bond_posted(self.bond.as_deref())?;
+
+        // Buy-it-now disappears once open bidding reaches it
+        let price = self.auction.buy_now_price.ok_or(BidError::BuyNowUnavailable)?;
+        require!(price > self.auction.highest_bid, BidError::BuyNowUnavailable);
+
+        let now = Clock::get()?.unix_timestamp;
+        self.auction.require_bidding_open(now)?;
+
+        // An existing bid only needs topping up to the buy-it-now price; a proxy
+        // maximum above it is left in escrow and the excess refunded at settlement
+        let top_up = if self.bid.bidder != Pubkey::default() {
+            require_keys_eq!(
+                self.bid.token_mint,
+                self.token_mint.key(),
+                BidError::PaymentMintChanged
+            );
+            let top_up = price.checked_sub(self.bid.amount).unwrap_or_default();
+            self.bid.amount = self.bid.amount.max(price);
+            self.bid.time_stamp = now;
+            top_up
+        } else {
+            self.bid.set_inner(Bid {
+                amount: price,
+                quantity: 1,
+                auction: self.auction.key(),
+                bidder: self.buyer.key(),
+                is_active: true,
+                is_winner: false,
+                commitment: None,
+                revealed: false,
+                time_stamp: now,
+                token_mint: self.token_mint.key(),
+                bump: bumps.bid,
+            });
+            price
+        };
+
+        // Transfer tokens to escrow, covering any transfer fee so it holds the full top-up
+        if top_up > 0 {
+            extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                self.buyer_token_account.to_account_info(),
+                self.token_mint.to_account_info(),
+                self.escrow_vault.to_account_info(),
+                self.buyer.to_account_info(),
+                hook_accounts,
+                extensions::gross_up(&self.token_mint.to_account_info(), top_up)?,
+                self.token_mint.decimals,
+                &[],
+            )?;
+        }
+
+        // Buyer wins outright; everyone else can withdraw straight away
+        self.auction.highest_bid = price;
+        self.auction.highest_bidder = self.buyer.key();
+        self.auction.leader_max = price;
+        self.auction.auction_status = AuctionStatus::Ended;
+
+        emit!(
+            AuctionBoughtNow {
+                auction: self.auction.key(),
+                buyer: self.buyer.key(),
+                price,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
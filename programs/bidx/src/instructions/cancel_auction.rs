@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    sync_native, Mint, SyncNative, TokenAccount, TokenInterface,
+};
+
+use crate::{
+    errors::{AuctionError, ConfigError},
+    events::AuctionCancelled,
+    bubblegum::{Leaf, LeafTransfer, COMPRESSED_ACCOUNTS},
+    extensions,
+    metaplex::{ItemTransfer, ProgrammableTransfer, PROGRAMMABLE_ACCOUNTS},
+    states::{Auction, AuctionStatus, ItemCustody, LeafProof, LotManifest, PlatformConfig, NATIVE_SOL},
+};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        seeds = [b"lot", auction.key().as_ref()],
+        bump = lot_manifest.bump
+    )]
+    pub lot_manifest: Option<Box<Account<'info, LotManifest>>>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    //NFT Accounts (left out for compressed NFTs)
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        address = auction.item_vault,
+        associated_token::mint = nft_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub item_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = seller,
+    )]
+    pub seller_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    //seller's payment token account (pays the cancellation fee, if any)
+    #[account(
+        mut,
+        token::authority = seller,
+    )]
+    pub seller_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    //Platform treasury
+    #[account(mut)]
+    pub treasury: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelAuction<'info> {
+    /// `remaining_accounts` carries a programmable NFT's transfer accounts
+    /// (see `ProgrammableTransfer::new`) or a compressed NFT's tree accounts
+    /// and `leaf_proof.proof_len` proof nodes (see `LeafTransfer::new`), then
+    /// the lot's (mint, vault, seller account) triples, then any accounts
+    /// transfer-hook mints need.
+    pub fn cancel_auction(
+        &mut self,
+        nonce: u64,
+        leaf_proof: Option<LeafProof>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let auction = &self.auction;
+        let custody_len = match &auction.item_custody {
+            ItemCustody::Token => 0,
+            ItemCustody::Programmable => PROGRAMMABLE_ACCOUNTS,
+            ItemCustody::Compressed { .. } => {
+                COMPRESSED_ACCOUNTS + leaf_proof.as_ref().map_or(0, |proof| proof.proof_len as usize)
+            }
+        };
+        let (custody_accounts, remaining_accounts) =
+            remaining_accounts.split_at(custody_len.min(remaining_accounts.len()));
+        let (lot_accounts, hook_accounts) = remaining_accounts
+            .split_at((auction.lot_items as usize * 3).min(remaining_accounts.len()));
+
+        // an auction that failed (or ended without bids, under its reserve, or
+        // past an unrevealed hidden reserve's deadline) sold nothing, so the
+        // seller takes the item back without a fee
+        let now = Clock::get()?.unix_timestamp;
+        let failed = auction.auction_status == AuctionStatus::Failed
+            || auction.has_failed(now)
+            || (auction.auction_status == AuctionStatus::Ended
+                && auction.highest_bidder == Pubkey::default());
+
+        //validations
+        require!(
+            failed
+                || auction.auction_status == AuctionStatus::Pending
+                || auction.auction_status == AuctionStatus::Active
+                || auction.auction_status == AuctionStatus::Paused,
+            AuctionError::CannotCancel
+        );
+
+        // Free before the first bid; afterwards only if the platform sets a fee
+        let cancellation_fee = if failed {
+            0
+        } else if auction.highest_bidder == Pubkey::default() {
+            // committed sealed bids hold deposits but have no price to charge a fee on
+            require!(auction.sealed_commitments == 0, AuctionError::BidsExist);
+            0
+        } else {
+            let cancellation_fee_bps = self.platform_config.cancellation_fee_bps;
+            require!(cancellation_fee_bps > 0, AuctionError::BidsExist);
+
+            (auction.highest_bid as u128)
+                .checked_mul(cancellation_fee_bps as u128)
+                .unwrap()
+                .checked_div(10_000)
+                .unwrap() as u64
+        };
+
+        // native SOL fees are paid from the seller's wallet into the wSOL treasury
+        if cancellation_fee > 0 && auction.accepted_token == NATIVE_SOL {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(AuctionError::CancellationFeeAccountsRequired)?;
+            require!(
+                treasury.key() == self.platform_config.treasury_sol,
+                AuctionError::InvalidTreasury
+            );
+
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.seller.to_account_info(),
+                        to: treasury.to_account_info(),
+                    },
+                ),
+                cancellation_fee,
+            )?;
+            sync_native(CpiContext::new(
+                self.token_program.to_account_info(),
+                SyncNative {
+                    account: treasury.to_account_info(),
+                },
+            ))?;
+        } else if cancellation_fee > 0 {
+            let (Some(seller_token_account), Some(treasury), Some(token_mint)) = (
+                self.seller_token_account.as_ref(),
+                self.treasury.as_ref(),
+                self.token_mint.as_ref(),
+            ) else {
+                return err!(AuctionError::CancellationFeeAccountsRequired);
+            };
+
+            require!(
+                token_mint.key() == auction.accepted_token
+                    && seller_token_account.mint == token_mint.key(),
+                AuctionError::WrongToken
+            );
+            // same treasuries settlement pays fees into
+            require!(
+                self.platform_config.is_fee_treasury(
+                    &self.platform_config.key(),
+                    treasury,
+                    &token_mint.key(),
+                ),
+                AuctionError::InvalidTreasury
+            );
+
+            extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                seller_token_account.to_account_info(),
+                token_mint.to_account_info(),
+                treasury.to_account_info(),
+                self.seller.to_account_info(),
+                hook_accounts,
+                cancellation_fee,
+                token_mint.decimals,
+                &[],
+            )?;
+        }
+
+        //PDA SEEDS
+        let seller_key = self.seller.key();
+        let auction_seeds = &[
+            b"auction",
+            seller_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[auction.bump]
+        ];
+
+        let auction_signer_seeds = &[&auction_seeds[..]];
+
+        // Return the item (every unit, for multi-unit auctions) to the seller
+        match (
+            &auction.item_custody,
+            self.nft_mint.as_deref(),
+            self.item_vault.as_deref(),
+            self.seller_nft_account.as_deref(),
+        ) {
+            (ItemCustody::Token, Some(nft_mint), Some(item_vault), Some(seller_nft_account)) => extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                item_vault.to_account_info(),
+                nft_mint.to_account_info(),
+                seller_nft_account.to_account_info(),
+                auction.to_account_info(),
+                hook_accounts,
+                item_vault.amount,
+                nft_mint.decimals,
+                auction_signer_seeds,
+            )?,
+            (ItemCustody::Programmable, Some(nft_mint), Some(item_vault), Some(seller_nft_account)) => {
+                ProgrammableTransfer::new(custody_accounts, &nft_mint.key())?.transfer(
+                    ItemTransfer {
+                        from: item_vault.to_account_info(),
+                        owner: auction.to_account_info(),
+                        to: seller_nft_account.to_account_info(),
+                        to_owner: self.seller.to_account_info(),
+                        mint: nft_mint.to_account_info(),
+                        payer: self.seller.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    item_vault.amount,
+                    auction_signer_seeds,
+                )?
+            }
+            (ItemCustody::Compressed { tree, leaf_index, data_hash, creator_hash }, ..) => {
+                let leaf_proof = leaf_proof.as_ref().ok_or(AuctionError::CompressedAccountsRequired)?;
+                LeafTransfer::new(custody_accounts, tree, leaf_proof.proof_len.into())?.transfer(
+                    auction.to_account_info(),
+                    self.seller.to_account_info(),
+                    self.system_program.to_account_info(),
+                    &Leaf {
+                        root: leaf_proof.root,
+                        data_hash: *data_hash,
+                        creator_hash: *creator_hash,
+                        leaf_index: *leaf_index,
+                    },
+                    auction_signer_seeds,
+                )?
+            }
+            _ => return err!(AuctionError::ItemAccountsRequired),
+        }
+
+        // Return the rest of the lot: remaining accounts are (mint, vault, seller account) per item
+        if auction.lot_items > 0 {
+            self.lot_manifest
+                .as_ref()
+                .ok_or(AuctionError::LotAccountsRequired)?
+                .transfer_items(
+                    lot_accounts,
+                    hook_accounts,
+                    &seller_key,
+                    self.token_program.to_account_info(),
+                    auction.to_account_info(),
+                    auction_signer_seeds,
+                )?;
+        }
+
+        // bidders withdraw their full escrow from a cancelled auction
+        self.auction.auction_status = AuctionStatus::Cancelled;
+
+        emit!(
+            AuctionCancelled {
+                auction: self.auction.key(),
+                seller: seller_key,
+                cancellation_fee,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
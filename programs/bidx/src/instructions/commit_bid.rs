@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::events::BidCommitted;
+use crate::states::{AuctionStatus, AuctionType, PlatformConfig, auction::Auction, bid::Bid, ParticipationBond};
+use crate::errors::{BidError, ConfigError};
+use crate::extensions;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        Mint, TokenAccount, TokenInterface,
+    },
+};
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", bidder.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(mut)]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Bidder's holding of the gating token, see `Auction::bidder_gate`
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Metaplex metadata of the held token, checked against its PDA for collection gates
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = token_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CommitBid<'info> {
+    pub fn commit_bid(
+        &mut self,
+        commitment: [u8; 32],
+        deposit: u64,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &CommitBidBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // Validations
+        require!(
+            self.auction.auction_status != AuctionStatus::Paused,
+            BidError::AuctionPaused
+        );
+        require!(
+            self.auction.auction_status == AuctionStatus::Active,
+            BidError::AuctionNotAvailable
+        );
+        require!(
+            self.token_mint.key() == self.auction.accepted_token,
+            BidError::WrongToken
+        );
+        require!(
+            matches!(self.auction.auction_type, AuctionType::SealedBid { .. }),
+            BidError::NotSealedBid
+        );
+        self.auction.require_allowlisted(&self.bidder.key(), allowlist_proof)?;
+        self.auction.require_gate_holder(
+            &self.bidder.key(),
+            self.gate_token_account.as_deref(),
+            self.gate_metadata.as_deref(),
+        )?;
+        self.auction.require_bond_posted(self.bond.as_deref())?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.auction.require_bidding_open(now)?;
+
+        // the deposit counts what escrow receives, net of any transfer fee
+        let received = extensions::received(&self.token_mint.to_account_info(), deposit)?;
+        if self.bid.bidder != Pubkey::default() {
+            // Re-commit: replace the hash and top up the deposit
+            self.bid.amount = self
+                .bid
+                .amount
+                .checked_add(received)
+                .ok_or(BidError::AmountOverflow)?;
+            self.bid.commitment = Some(commitment);
+            self.bid.time_stamp = now;
+        } else {
+            self.auction.sealed_commitments += 1;
+            self.bid.set_inner(Bid {
+                amount: received,
+                quantity: 1,
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                is_active: true,
+                is_winner: false,
+                commitment: Some(commitment),
+                revealed: false,
+                time_stamp: now,
+                token_mint: self.token_mint.key(),
+                bump: bumps.bid,
+            });
+        }
+
+        require!(self.bid.amount > 0, BidError::BidTooLow);
+
+        // Transfer deposit to escrow
+        if deposit > 0 {
+            extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                self.bidder_token_account.to_account_info(),
+                self.token_mint.to_account_info(),
+                self.escrow_vault.to_account_info(),
+                self.bidder.to_account_info(),
+                hook_accounts,
+                deposit,
+                self.token_mint.decimals,
+                &[],
+            )?;
+        }
+
+        emit!(
+            BidCommitted {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                deposit: self.bid.amount,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -1,9 +1,19 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::{
+    token::ID as TOKEN_PROGRAM_ID,
+    token_2022::ID as TOKEN_2022_PROGRAM_ID,
+    token_interface::{Mint, TokenAccount},
+};
 
 use crate::events::AuctionCreated;
-use crate::states::{AssetType, Auction, AuctionStatus, AuthStatus, Authentication, AuthenticatorsRegistry, SellerState};
-use crate::errors::{ AuctionAuthError, AuctionError};
+use crate::states::{
+    AssetType, Auction, AuctionStatus, AuctionTerms, CompressedLeaf, ItemCustody, AuctionType, AuthStatus, Authentication, AuthenticatorsRegistry,
+    LotItem, LotManifest, PlatformConfig, SellerState, MAX_LOT_ITEMS, NATIVE_SOL,
+};
+use crate::errors::{ AuctionAuthError, AuctionError, BidError, ConfigError};
+use crate::bubblegum::{self, Leaf, LeafTransfer, COMPRESSED_ACCOUNTS};
+use crate::extensions;
+use crate::metaplex::{self, ItemTransfer, ProgrammableTransfer, PROGRAMMABLE_ACCOUNTS};
 
 #[derive(Accounts)]
 pub struct CreateAuction<'info> {
@@ -26,8 +36,20 @@ pub struct CreateAuction<'info> {
         bump
     )]
     pub auction: Account<'info, Auction>,
-    pub nft_mint: InterfaceAccount<'info, Mint>,
-    pub item_vault: InterfaceAccount<'info, TokenAccount>,
+    // left out, with item_vault, for compressed NFTs
+    pub nft_mint: Option<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Metaplex metadata of nft_mint, checked against its PDA; marks programmable NFTs
+    pub nft_metadata: Option<UncheckedAccount<'info>>,
+    // where a programmable NFT is moved into item_vault from
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = seller,
+    )]
+    pub seller_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    // mint of accepted_token, left out for native SOL
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub item_vault: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init,
         payer = seller,
@@ -36,12 +58,32 @@ pub struct CreateAuction<'info> {
         bump
     )]
     pub authentication: Account<'info, Authentication>,
-    #[account(mut)]
+    // the platform's own registry, so the seller can't pick their authenticator
+    #[account(
+        mut,
+        seeds = [b"authenticators_registry", platform_config.admin.as_ref()],
+        bump = registry.bump
+    )]
     pub registry: Account<'info, AuthenticatorsRegistry>,
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + LotManifest::INIT_SPACE,
+        seeds = [b"lot", auction.key().as_ref()],
+        bump
+    )]
+    pub lot_manifest: Option<Box<Account<'info, LotManifest>>>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreateAuction<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &mut self,
         accepted_token: Pubkey,
@@ -51,16 +93,247 @@ impl<'info> CreateAuction<'info> {
         end_date: i64,
         asset_type: AssetType,
         bumps: & CreateAuctionBumps,
-        document_hash: Option<String>
+        document_hash: Option<String>,
+        auction_type: AuctionType,
+        terms: AuctionTerms,
+        compressed_leaf: Option<CompressedLeaf>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
-        let seller_state = &mut self.seller_state;
+        let AuctionTerms {
+            soft_close,
+            buy_now_price,
+            min_increment,
+            multi_unit,
+            hidden_reserve,
+            allowlist_root,
+            bidder_gate,
+            participation_bond,
+            payment_options,
+            usd_pricing,
+        } = terms;
 
         //checks
         require!(start_date > Clock::get()?.unix_timestamp, AuctionError::StartDateIsBehind);
-        require!(end_date > start_date, AuctionError::EndDateIsBehindStartDate);
-        require!(reserved_price > starting_bid, AuctionError::ReservedPriceTooLow);
-        
+
+        let auth_status =  if asset_type == AssetType::DigitalNFT{
+            AuthStatus::NotRequired
+        } else {
+            AuthStatus::Pending
+        };
+
+        // the item is either a token held in item_vault or a compressed leaf
+        let (item_custody, nft_mint, item_vault) =
+            match (compressed_leaf.as_ref(), self.nft_mint.as_ref(), self.item_vault.as_ref()) {
+                (None, Some(nft_mint), Some(item_vault)) => {
+                    let item_custody = match self.nft_metadata.as_ref() {
+                        Some(metadata) => {
+                            require!(
+                                *metadata.owner == metaplex::TOKEN_METADATA_PROGRAM_ID
+                                    && metadata.key() == metaplex::metadata_pda(&nft_mint.key()),
+                                AuctionError::InvalidItemMetadata
+                            );
+                            if metaplex::is_programmable(&metadata.try_borrow_data()?) {
+                                ItemCustody::Programmable
+                            } else {
+                                ItemCustody::Token
+                            }
+                        }
+                        None => ItemCustody::Token,
+                    };
+                    (item_custody, nft_mint.key(), item_vault.key())
+                }
+                (Some(leaf), None, None) => {
+                    let tree = remaining_accounts
+                        .get(2)
+                        .ok_or(AuctionError::CompressedAccountsRequired)?
+                        .key();
+                    let item_custody = ItemCustody::Compressed {
+                        tree,
+                        leaf_index: leaf.leaf_index,
+                        data_hash: leaf.data_hash,
+                        creator_hash: leaf.creator_hash,
+                    };
+                    (item_custody, bubblegum::asset_id(&tree, leaf.leaf_index.into()), Pubkey::default())
+                }
+                (Some(_), _, _) => return err!(AuctionError::InvalidCompressedItem),
+                (None, _, _) => return err!(AuctionError::ItemAccountsRequired),
+            };
+
+        // the item's own transfer accounts (pNFT accounts, or a compressed
+        // NFT's tree accounts and proof) come first, then the lot
+        let (custody_accounts, lot_accounts) = remaining_accounts.split_at(match item_custody {
+            ItemCustody::Token => 0,
+            ItemCustody::Programmable => PROGRAMMABLE_ACCOUNTS.min(remaining_accounts.len()),
+            ItemCustody::Compressed { .. } => remaining_accounts.len(),
+        });
+
+        let mut auction = Auction {
+            seller: self.seller.key(),
+            platform_config: self.platform_config.key(),
+            accepted_token,
+            payment_options,
+            reserved_price,
+            usd_pricing,
+            hidden_reserve,
+            allowlist_root,
+            bidder_gate,
+            participation_bond,
+            starting_bid,
+            buy_now_price,
+            min_increment,
+            multi_unit,
+            units_settled: 0,
+            lot_items: 0,
+            start_date,
+            end_date,
+            soft_close,
+            extension_count: 0,
+            paused_at: 0,
+            auction_status: AuctionStatus::Pending, // opened by activate_auction
+            auth_status,
+            item_vault,
+            item_custody,
+            nft_mint,
+            asset_type,
+            auction_type,
+            highest_bid: 0,
+            highest_bidder: Pubkey::default(),
+            leader_max: 0,
+            second_highest_bid: 0,
+            sealed_commitments: 0,
+            bump: bumps.auction
+        };
+        auction.validate_terms(&self.platform_config)?;
+
+        // reject Token-2022 extensions up front rather than failing transfers later
+        if let Some(nft_mint) = self.nft_mint.as_ref() {
+            extensions::require_supported_item_mint(&nft_mint.to_account_info())?;
+        }
+        if accepted_token != NATIVE_SOL {
+            let token_mint = self.token_mint.as_ref().ok_or(BidError::TokenAccountsRequired)?;
+            require_keys_eq!(token_mint.key(), accepted_token, AuctionError::WrongToken);
+            extensions::require_supported_payment_mint(&token_mint.to_account_info())?;
+        }
+
+        match (&auction.item_custody, self.nft_mint.as_ref(), self.item_vault.as_ref()) {
+            (ItemCustody::Token, Some(nft_mint), Some(item_vault)) => {
+                // a frozen vault could never be paid out with transfer_checked
+                require!(!item_vault.is_frozen(), AuctionError::ItemVaultFrozen);
+
+                // the vault must already hold every unit on sale
+                if let Some(multi_unit) = auction.multi_unit.as_ref() {
+                    require!(
+                        item_vault.mint == nft_mint.key() && item_vault.amount >= multi_unit.quantity,
+                        AuctionError::InvalidMultiUnit
+                    );
+                }
+            }
+            // pNFTs sit frozen, so Token Metadata moves the item into custody here
+            (ItemCustody::Programmable, Some(nft_mint), Some(item_vault)) => {
+                require!(auction.multi_unit.is_none(), AuctionError::InvalidMultiUnit);
+                let seller_nft_account = self
+                    .seller_nft_account
+                    .as_ref()
+                    .ok_or(AuctionError::ProgrammableAccountsRequired)?;
+
+                ProgrammableTransfer::new(custody_accounts, &nft_mint.key())?.transfer(
+                    ItemTransfer {
+                        from: seller_nft_account.to_account_info(),
+                        owner: self.seller.to_account_info(),
+                        to: item_vault.to_account_info(),
+                        to_owner: self.auction.to_account_info(),
+                        mint: nft_mint.to_account_info(),
+                        payer: self.seller.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    1,
+                    &[],
+                )?;
+            }
+            // Bubblegum hands the leaf to the auction PDA, proving the seller held it
+            (ItemCustody::Compressed { tree, leaf_index, data_hash, creator_hash }, None, None) => {
+                require!(
+                    auction.multi_unit.is_none() && self.lot_manifest.is_none(),
+                    AuctionError::InvalidCompressedItem
+                );
+                let root = compressed_leaf.as_ref().ok_or(AuctionError::InvalidCompressedItem)?.root;
+
+                LeafTransfer::new(
+                    custody_accounts,
+                    tree,
+                    custody_accounts.len().saturating_sub(COMPRESSED_ACCOUNTS),
+                )?
+                .transfer(
+                    self.seller.to_account_info(),
+                    self.auction.to_account_info(),
+                    self.system_program.to_account_info(),
+                    &Leaf {
+                        root,
+                        data_hash: *data_hash,
+                        creator_hash: *creator_hash,
+                        leaf_index: *leaf_index,
+                    },
+                    &[],
+                )?;
+            }
+            _ => return err!(AuctionError::ItemAccountsRequired),
+        }
+
+        // remaining accounts come in (mint, vault) pairs, one per extra lot item
+        let lot_items = match self.lot_manifest.as_mut() {
+            Some(lot_manifest) => {
+                require!(
+                    auction.multi_unit.is_none()
+                        && !lot_accounts.is_empty()
+                        && lot_accounts.len() % 2 == 0
+                        && lot_accounts.len() / 2 <= MAX_LOT_ITEMS,
+                    AuctionError::InvalidLot
+                );
+
+                let auction_key = self.auction.key();
+                let mut items: Vec<LotItem> = Vec::with_capacity(lot_accounts.len() / 2);
+                for pair in lot_accounts.chunks(2) {
+                    let (mint_info, vault_info) = (&pair[0], &pair[1]);
+                    require!(
+                        (*vault_info.owner == TOKEN_PROGRAM_ID || *vault_info.owner == TOKEN_2022_PROGRAM_ID)
+                            && mint_info.owner == vault_info.owner
+                            && vault_info.key() != auction.item_vault
+                            && items.iter().all(|item| item.vault != vault_info.key()),
+                        AuctionError::InvalidLot
+                    );
+
+                    extensions::require_supported_item_mint(mint_info)?;
+                    let vault = TokenAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+                    require!(
+                        vault.mint == mint_info.key()
+                            && vault.owner == auction_key
+                            && vault.amount > 0
+                            && !vault.is_frozen(),
+                        AuctionError::InvalidLot
+                    );
+
+                    items.push(LotItem {
+                        mint: mint_info.key(),
+                        vault: vault_info.key(),
+                        amount: vault.amount,
+                    });
+                }
+
+                lot_manifest.auction = auction_key;
+                lot_manifest.items = items;
+                lot_manifest.bump = bumps.lot_manifest.unwrap_or_default();
+                lot_manifest.items.len() as u8
+            }
+            None => {
+                require!(lot_accounts.is_empty(), AuctionError::InvalidLot);
+                0
+            }
+        };
+
+        auction.lot_items = lot_items;
+
         // first auction creation
+        let seller_state = &mut self.seller_state;
         if seller_state.auction_count == 0 {
             seller_state.seller = self.seller.key();
             seller_state.bump = bumps.seller_state;
@@ -68,7 +341,7 @@ impl<'info> CreateAuction<'info> {
 
         self.seller_state.auction_count += 1;
 
-        if asset_type == AssetType::PhysicalRWA {
+        if auction.asset_type == AssetType::PhysicalRWA {
             //Assign authenticator (using round robin)
             let registry = &mut self.registry;
 
@@ -97,36 +370,7 @@ impl<'info> CreateAuction<'info> {
             self.authentication.bump = bumps.authentication;
         }
 
-        let auth_status =  if asset_type == AssetType::DigitalNFT{
-            AuthStatus::NotRequired
-        } else {
-            AuthStatus::Pending
-        };
-
-       let auction_status = if asset_type == AssetType::DigitalNFT {
-            AuctionStatus::Active
-        } else {
-            AuctionStatus::Pending
-        };
-
-        self.auction.set_inner({
-            Auction {
-                seller: self.seller.key(),
-                accepted_token,
-                reserved_price,
-                starting_bid,
-                start_date,
-                end_date,
-                auction_status: auction_status,
-                auth_status,
-                item_vault: self.item_vault.key(),
-                nft_mint: self.nft_mint.key(),  
-                asset_type,
-                highest_bid: 0,
-                highest_bidder: Pubkey::default(),
-                bump: bumps.auction
-            }
-        });
+        self.auction.set_inner(auction);
 
         emit!(
             AuctionCreated {
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::AuctionError;
-use crate::states::{Auction, AuctionStatus};
+use crate::states::{Auction, AuctionStatus, AuctionType};
 
 #[derive(Accounts)]
 #[instruction(nonce: u64)]
@@ -24,12 +24,30 @@ impl<'info> EndAuction<'info> {
             return Ok(());
         }
 
+        let now = Clock::get()?.unix_timestamp;
+
+        // Sealed-bid auctions close into a reveal phase before they end
+        if let AuctionType::SealedBid { reveal_end_date, .. } = self.auction.auction_type {
+            require!(
+                self.auction.auction_status == AuctionStatus::Active
+                    || self.auction.auction_status == AuctionStatus::Revealing,
+                AuctionError::AuctionNotEnded
+            );
+            require!(now >= self.auction.end_date, AuctionError::AuctionNotEnded);
+
+            self.auction.auction_status = if now >= reveal_end_date {
+                AuctionStatus::Ended
+            } else {
+                AuctionStatus::Revealing
+            };
+            return Ok(());
+        }
+
         require!(
             self.auction.auction_status == AuctionStatus::Active,
             AuctionError::AuctionNotEnded
         );
 
-        let now = Clock::get()?.unix_timestamp;
         require!(now >= self.auction.end_date, AuctionError::AuctionNotEnded);
 
         self.auction.auction_status = AuctionStatus::Ended;
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::errors::{AuctionError, BidError};
+use crate::events::AuctionFailed;
+use crate::states::{Auction, AuctionStatus, NATIVE_SOL};
+
+/// Fails an ended USD-priced auction whose leader's maximum the oracle prices
+/// under the reserve, so bidders are refunded and the seller takes the item
+/// back without waiting on the winner to attempt settlement. Anyone can call it.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct FailAuction<'info> {
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+
+    /// CHECK: Pyth price update, owner and layout checked in Auction::usd_clearing_price
+    pub price_update: UncheckedAccount<'info>,
+
+    // mint of accepted_token, left out for native SOL
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> FailAuction<'info> {
+    pub fn fail_auction(&mut self) -> Result<()> {
+        let auction = &self.auction;
+        require!(
+            auction.auction_status == AuctionStatus::Ended,
+            AuctionError::AuctionNotEnded
+        );
+        require!(auction.usd_pricing.is_some(), AuctionError::InvalidUsdPricing);
+
+        let decimals = match self.token_mint.as_ref() {
+            Some(token_mint) => {
+                require_keys_eq!(token_mint.key(), auction.accepted_token, BidError::WrongToken);
+                token_mint.decimals
+            }
+            None => {
+                require_keys_eq!(auction.accepted_token, NATIVE_SOL, BidError::WrongToken);
+                9
+            }
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        let clearing_price = auction.usd_clearing_price(
+            Some(&self.price_update.to_account_info()),
+            decimals,
+            now,
+        )?;
+        require!(clearing_price.is_none(), AuctionError::ReserveMet);
+
+        self.auction.auction_status = AuctionStatus::Failed;
+
+        emit!(
+            AuctionFailed {
+                auction: self.auction.key(),
+                highest_bid: self.auction.highest_bid,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::{AuctionError, BidError, ConfigError};
+use crate::events::BondForfeited;
+use crate::states::{Auction, AuctionStatus, Bid, ForfeitReason, ParticipationBond, PlatformConfig, SETTLEMENT_WINDOW};
+
+/// Sends a bidder's bond to the treasury for shill bidding (over a bid that is
+/// still open) or for abandoning a won lot (no settlement within
+/// `SETTLEMENT_WINDOW`). Bonds are only forfeitable until released.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ForfeitBond<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        has_one = admin
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    /// CHECK: bond owner, receives the bond account's rent
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub bond: Box<Account<'info, ParticipationBond>>,
+
+    /// CHECK: the bidder's bid PDA, which must still be open
+    #[account(
+        seeds = [b"bid", bidder.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bid: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    //Platform treasury, per-mint vaults included
+    #[account(
+        mut,
+        constraint = platform_config.is_fee_treasury(
+            &platform_config.key(),
+            &treasury,
+            &token_mint.key(),
+        ) @ AuctionError::InvalidTreasury
+    )]
+    pub treasury: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ForfeitBond<'info> {
+    pub fn forfeit_bond(
+        &mut self,
+        nonce: u64,
+        reason: ForfeitReason,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let auction = &self.auction;
+        let bid_open = !self.bid.data_is_empty()
+            && Bid::try_deserialize(&mut &self.bid.try_borrow_data()?[..])?.is_active;
+        let forfeitable = match reason {
+            ForfeitReason::ShillBidding => {
+                bid_open
+                    && matches!(
+                        auction.auction_status,
+                        AuctionStatus::Active
+                            | AuctionStatus::Paused
+                            | AuctionStatus::Revealing
+                            | AuctionStatus::Ended
+                    )
+            }
+            ForfeitReason::AbandonedLot => {
+                bid_open
+                    && auction.auction_status == AuctionStatus::Ended
+                    && auction.multi_unit.is_none()
+                    && auction.highest_bidder == self.bidder.key()
+                    && Clock::get()?.unix_timestamp
+                        >= auction.bidding_closes().saturating_add(SETTLEMENT_WINDOW)
+            }
+        };
+        require!(forfeitable, BidError::BondNotForfeitable);
+
+        //PDA signer
+        let seller_key = self.seller.key();
+        let auction_seeds = &[
+            b"auction",
+            seller_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[self.auction.bump]
+        ];
+        let auction_signer_seeds = &[&auction_seeds[..]];
+
+        self.bond.pay_out(
+            &self.auction,
+            &self.bond_vault,
+            self.treasury.to_account_info(),
+            &self.token_mint,
+            self.token_program.to_account_info(),
+            hook_accounts,
+            auction_signer_seeds,
+        )?;
+
+        emit!(
+            BondForfeited {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                amount: self.bond.amount,
+                reason,
+            }
+        );
+
+        Ok(())
+    }
+}
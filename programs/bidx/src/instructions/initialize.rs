@@ -71,7 +71,8 @@ impl<'info> InitializePlatform<'info> {
                 auth_fee_bps,
                 treasury_sol: self.treasury_sol.key(),
                 treasury_usdc: self.treasury_usdc.key(),
-                bump: bumps.platform_config
+                bump: bumps.platform_config,
+                cancellation_fee_bps: 0,
         });
 
         self.authenticators_registry.set_inner({
@@ -11,6 +11,21 @@ pub mod update_platform_config;
 pub mod settle;
 pub mod toggle_pause_platform;
 pub mod withdraw_bid;
+pub mod commit_bid;
+pub mod reveal_bid;
+pub mod buy_now;
+pub mod bid_units;
+pub mod reveal_reserve;
+pub mod cancel_auction;
+pub mod pause_auction;
+pub mod activate_auction;
+pub mod amend_auction;
+pub mod post_bond;
+pub mod withdraw_bond;
+pub mod forfeit_bond;
+pub mod open_treasury_vault;
+pub mod reclaim_unsold_units;
+pub mod fail_auction;
 
 pub use create_auction::*;
 pub use close_platform::*;
@@ -25,3 +40,18 @@ pub use update_platform_config::*;
 pub use settle::*;
 pub use toggle_pause_platform::*;
 pub use withdraw_bid::*;
+pub use commit_bid::*;
+pub use reveal_bid::*;
+pub use buy_now::*;
+pub use bid_units::*;
+pub use reveal_reserve::*;
+pub use cancel_auction::*;
+pub use pause_auction::*;
+pub use activate_auction::*;
+pub use amend_auction::*;
+pub use post_bond::*;
+pub use withdraw_bond::*;
+pub use forfeit_bond::*;
+pub use open_treasury_vault::*;
+pub use reclaim_unsold_units::*;
+pub use fail_auction::*;
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::ConfigError,
+    events::TreasuryVaultOpened,
+    states::PlatformConfig
+};
+
+#[derive(Accounts)]
+pub struct OpenTreasuryVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        has_one = admin @ ConfigError::ExclusiveToAdmin,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = token_mint,
+        associated_token::authority = platform_config,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenTreasuryVault<'info> {
+    /// Fee vault for an extra payment mint; settlement takes fees in whatever
+    /// mint the winning bid was escrowed in.
+    pub fn open_treasury_vault(&mut self) -> Result<()> {
+        emit!(
+            TreasuryVaultOpened {
+                mint: self.token_mint.key(),
+                vault: self.treasury_vault.key(),
+                timestamp: Clock::get()?.unix_timestamp
+            }
+        );
+
+        Ok(())
+    }
+}
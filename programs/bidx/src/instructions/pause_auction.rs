@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::{AuctionError, ConfigError},
+    events::{AuctionPaused, AuctionResumed},
+    states::{Auction, AuctionStatus, AuctionType, PlatformConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct PauseAuction<'info> {
+    // seller or platform admin
+    pub authority: Signer<'info>,
+
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+impl<'info> PauseAuction<'info> {
+    pub fn pause_auction(&mut self) -> Result<()> {
+        self.require_seller_or_admin()?;
+        require!(
+            self.auction.auction_status == AuctionStatus::Active,
+            AuctionError::CannotPause
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < self.auction.end_date, AuctionError::CannotPause);
+
+        self.auction.auction_status = AuctionStatus::Paused;
+        self.auction.paused_at = now;
+
+        emit!(
+            AuctionPaused {
+                auction: self.auction.key(),
+                paused_by: self.authority.key(),
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Reopens bidding and pushes every deadline back by the time spent
+    /// paused, so bidders keep the bidding time they had left.
+    pub fn resume_auction(&mut self) -> Result<()> {
+        self.require_seller_or_admin()?;
+        require!(
+            self.auction.auction_status == AuctionStatus::Paused,
+            AuctionError::NotPaused
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let auction = &mut self.auction;
+        let paused_for = now - auction.paused_at;
+
+        auction.end_date += paused_for;
+        match &mut auction.auction_type {
+            AuctionType::SealedBid { reveal_end_date, .. } => *reveal_end_date += paused_for,
+            // the price clock stops while paused
+            AuctionType::Dutch { .. } => auction.start_date += paused_for,
+            AuctionType::English => {}
+        }
+        if let Some(hidden_reserve) = auction.hidden_reserve.as_mut() {
+            hidden_reserve.reveal_deadline += paused_for;
+        }
+
+        auction.auction_status = AuctionStatus::Active;
+        auction.paused_at = 0;
+
+        emit!(
+            AuctionResumed {
+                auction: auction.key(),
+                resumed_by: self.authority.key(),
+                paused_for,
+                new_end_date: auction.end_date,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+
+    fn require_seller_or_admin(&self) -> Result<()> {
+        require!(
+            self.authority.key() == self.auction.seller
+                || self.authority.key() == self.platform_config.admin,
+            AuctionError::NotSellerOrAdmin
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::events::BidPlaced;
-use crate::states::{AuctionStatus, auction::Auction, bid::Bid};
-use crate::errors::{BidError};
+use crate::events::{AuctionExtended, BidPlaced, ProxyBidRaised};
+use crate::states::{AuctionStatus, AuctionType, Escrow, PlatformConfig, auction::Auction, bid::Bid, ParticipationBond};
+use crate::errors::{BidError, ConfigError};
+use crate::extensions;
 
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{
-        transfer_checked, Mint, TokenAccount, TokenInterface,
-        TransferChecked,
-    },
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 #[derive(Accounts)]
@@ -28,14 +26,38 @@ pub struct PlaceBid<'info> {
     
     #[account(mut)]
     pub auction: Account<'info, Auction>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Pyth price update pricing a USD starting bid, owner and layout checked in Auction::opening_bid
+    pub price_update: Option<UncheckedAccount<'info>>,
+
+    /// Bidder's holding of the gating token, see `Auction::bidder_gate`
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Metaplex metadata of the held token, checked against its PDA for collection gates
+    pub gate_metadata: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
     
+    // token accounts are left out of native SOL auctions, whose escrow is the bid PDA
     #[account(
         mut,
         associated_token::mint = token_mint,
         associated_token::authority = bidder,
         associated_token::token_program = token_program
     )]
-    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub bidder_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
     #[account(
         init_if_needed,
@@ -44,86 +66,275 @@ pub struct PlaceBid<'info> {
         associated_token::authority = bid,
         associated_token::token_program = token_program
     )]
-    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         mint::token_program = token_program
     )]
-    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> PlaceBid<'info> {
-    pub fn place_bid(&mut self, amount: u64, bumps: &PlaceBidBumps) -> Result<()> {
+    pub fn place_bid(
+        &mut self,
+        amount: u64,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &PlaceBidBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        self.submit_bid(amount, false, allowlist_proof, bumps, hook_accounts)
+    }
+
+    /// Escrows `amount` towards a maximum the program bids up to on the
+    /// bidder's behalf, one increment at a time.
+    pub fn place_proxy_bid(
+        &mut self,
+        amount: u64,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &PlaceBidBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            self.auction.auction_type == AuctionType::English,
+            BidError::ProxyBidUnavailable
+        );
+        self.submit_bid(amount, true, allowlist_proof, bumps, hook_accounts)
+    }
+
+    fn submit_bid(
+        &mut self,
+        amount: u64,
+        proxy: bool,
+        allowlist_proof: &[[u8; 32]],
+        bumps: &PlaceBidBumps,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         // Validations
+        require!(
+            self.auction.auction_status != AuctionStatus::Paused,
+            BidError::AuctionPaused
+        );
         require!(
             self.auction.auction_status == AuctionStatus::Active,
             BidError::AuctionNotAvailable
         );
+        // bids are escrowed in accepted_token or one of the auction's payment options
+        let payment_mint = self
+            .token_mint
+            .as_ref()
+            .map_or(self.auction.accepted_token, |mint| mint.key());
         require!(
-            self.token_mint.key() == self.auction.accepted_token,
+            self.auction.payment_rate(&payment_mint).is_some(),
             BidError::WrongToken
         );
-        
-        // Check if increasing existing bid or new bid
         if self.bid.bidder != Pubkey::default() {
-            // Increasing existing bid
-            let new_total = self.bid.amount + amount;
-            require!(
-                new_total > self.auction.highest_bid,
-                BidError::BidTooLow
-            );
-            
-            self.bid.amount = new_total;
-            self.bid.time_stamp = Clock::get()?.unix_timestamp;
-        } else {
-            // First bid
-            require!(
-                amount > self.auction.highest_bid,
-                BidError::BidTooLow
-            );
-            
-            self.bid.set_inner(Bid {
-                amount,
-                auction: self.auction.key(),
-                bidder: self.bidder.key(),
-                is_active: true,
-                is_winner: false,
-                time_stamp: Clock::get()?.unix_timestamp,
-                token_mint: self.token_mint.key(),
-                bump: bumps.bid,
-            });
+            require_keys_eq!(payment_mint, self.bid.token_mint, BidError::PaymentMintChanged);
         }
-        
-        // Transfer tokens to escrow
-        transfer_checked(
-            CpiContext::new(
-                self.token_program.to_account_info(),
-                TransferChecked {
-                    from: self.bidder_token_account.to_account_info(),
-                    to: self.escrow_vault.to_account_info(),
-                    mint: self.token_mint.to_account_info(),
-                    authority: self.bidder.to_account_info(),
-                },
-            ),
-            amount,
-            self.token_mint.decimals,
+        if let Some(token_mint) = self.token_mint.as_ref() {
+            extensions::require_supported_payment_mint(&token_mint.to_account_info())?;
+        }
+        let escrow = Escrow::new(
+            &payment_mint,
+            self.escrow_vault.as_deref(),
+            self.token_mint.as_deref(),
+            &self.token_program.to_account_info(),
+            hook_accounts,
         )?;
+        require!(
+            self.auction.multi_unit.is_none(),
+            BidError::MultiUnitOnly
+        );
+        self.auction.require_allowlisted(&self.bidder.key(), allowlist_proof)?;
+        self.auction.require_gate_holder(
+            &self.bidder.key(),
+            self.gate_token_account.as_deref(),
+            self.gate_metadata.as_deref(),
+        )?;
+        self.auction.require_bond_posted(self.bond.as_deref())?;
         
-        // Update auction state
-        self.auction.highest_bid = self.bid.amount;
-        self.auction.highest_bidder = self.bidder.key();
+        let now = Clock::get()?.unix_timestamp;
+        self.auction.require_bidding_open(now)?;
+
+        let escrow_amount = match self.auction.auction_type {
+            AuctionType::English => {
+                // bids count what escrow receives, net of any transfer fee
+                let received = escrow.received(amount)?;
+                // Check if increasing existing bid or new bid
+                let new_total = if self.bid.bidder != Pubkey::default() {
+                    self.bid.amount
+                        .checked_add(received)
+                        .ok_or(BidError::AmountOverflow)?
+                } else {
+                    received
+                };
+
+                // bids in other payment mints compete at their base-unit value
+                let offer = self.auction.to_base(&payment_mint, new_total)?;
+                let min_next_bid = self.auction.min_next_bid(
+                    self.price_update.as_deref(),
+                    escrow.decimals(),
+                    now,
+                )?;
+                if offer < min_next_bid {
+                    msg!("Minimum acceptable bid: {}", min_next_bid);
+                    return err!(BidError::BelowMinimumBid);
+                }
+
+                if self.bid.bidder != Pubkey::default() {
+                    // Increasing existing bid
+                    self.bid.amount = new_total;
+                    self.bid.time_stamp = now;
+                } else {
+                    // First bid
+                    self.record_new_bid(received, payment_mint, now, bumps);
+                }
+                self.resolve_english_bid(offer, min_next_bid, proxy, now);
+                amount
+            }
+            AuctionType::Dutch { .. } => {
+                // First taker at (or above) the current price wins outright
+                let price = self.auction.dutch_price(now).unwrap_or_default();
+                require!(amount >= price, BidError::BelowDutchPrice);
+
+                self.record_new_bid(price, payment_mint, now, bumps);
+                self.auction.highest_bid = price;
+                self.auction.highest_bidder = self.bidder.key();
+                self.auction.auction_status = AuctionStatus::Ended;
+                // the taker covers the transfer fee so escrow holds the full price
+                escrow.gross_up(price)?
+            }
+            AuctionType::SealedBid { .. } => return err!(BidError::SealedBidOnly),
+        };
+
+        // Transfer tokens (or lamports) to escrow
+        escrow.deposit(
+            &self.bidder.to_account_info(),
+            self.bidder_token_account.as_ref().map(|account| account.to_account_info()),
+            &self.bid.to_account_info(),
+            &self.system_program.to_account_info(),
+            escrow_amount,
+        )?;
+
+
+        // Anti-sniping: late bids push the end date back
+        self.extend_if_late_bid(now)?;
 
         emit!(
             BidPlaced {
                 auction: self.auction.key(),
-                bid_amount: amount,
+                bid_amount: escrow_amount,
                 bidder: self.bidder.key(),
-                timestamp: Clock::get()?.unix_timestamp,
+                timestamp: now,
             }
         );
         
         Ok(())
     }
+
+    /// Resolves an English bid against the current leader's escrowed maximum.
+    /// Plain bids stand at their full amount; proxy bids (and a leader's proxy
+    /// defending its lead) stand at the second-highest maximum plus one
+    /// increment, an opening proxy bid at `min_next_bid`. Ties go to the
+    /// earlier bid.
+    fn resolve_english_bid(&mut self, offer: u64, min_next_bid: u64, proxy: bool, now: i64) {
+        let auction = &self.auction;
+        let bidder = self.bidder.key();
+        let leader = auction.highest_bidder;
+
+        let (highest_bidder, standing, max) = if leader == Pubkey::default() || leader == bidder {
+            let standing = match (proxy, leader == bidder) {
+                (false, _) => offer,
+                (true, true) => auction.highest_bid,
+                (true, false) => min_next_bid,
+            };
+            (bidder, standing, offer)
+        } else if offer > auction.leader_max {
+            let standing = if proxy {
+                auction.raise_over(auction.leader_max, offer)
+            } else {
+                offer
+            };
+            (bidder, standing, offer)
+        } else {
+            (leader, auction.raise_over(offer, auction.leader_max), auction.leader_max)
+        };
+
+        // a maximum that covers the reserve bids at least the reserve
+        let highest_bid = if auction.usd_pricing.is_none() && max >= auction.reserved_price {
+            standing.max(auction.reserved_price)
+        } else {
+            standing
+        };
+
+        let defended = highest_bidder != bidder;
+        self.auction.highest_bidder = highest_bidder;
+        self.auction.highest_bid = highest_bid;
+        self.auction.leader_max = max;
+
+        if defended {
+            emit!(
+                ProxyBidRaised {
+                    auction: self.auction.key(),
+                    bidder: highest_bidder,
+                    highest_bid,
+                    timestamp: now,
+                }
+            );
+        }
+    }
+
+    fn record_new_bid(&mut self, amount: u64, token_mint: Pubkey, now: i64, bumps: &PlaceBidBumps) {
+        self.bid.set_inner(Bid {
+            amount,
+            quantity: 1,
+            auction: self.auction.key(),
+            bidder: self.bidder.key(),
+            is_active: true,
+            is_winner: false,
+            commitment: None,
+            revealed: false,
+            time_stamp: now,
+            token_mint,
+            bump: bumps.bid,
+        });
+    }
+
+    fn extend_if_late_bid(&mut self, now: i64) -> Result<()> {
+        let Some(soft_close) = self.auction.soft_close.clone() else {
+            return Ok(());
+        };
+
+        let previous_end_date = self.auction.end_date;
+        if now >= previous_end_date || previous_end_date - now > soft_close.window {
+            return Ok(());
+        }
+
+        if let Some(max_extensions) = soft_close.max_extensions {
+            if self.auction.extension_count >= max_extensions {
+                return Ok(());
+            }
+        }
+
+        let new_end_date = now + soft_close.extension;
+        if new_end_date <= previous_end_date {
+            return Ok(());
+        }
+
+        self.auction.end_date = new_end_date;
+        self.auction.extension_count += 1;
+
+        emit!(
+            AuctionExtended {
+                auction: self.auction.key(),
+                previous_end_date,
+                new_end_date,
+                extension_count: self.auction.extension_count,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::errors::{BidError, ConfigError};
+use crate::extensions;
+use crate::events::BondPosted;
+use crate::states::{Auction, AuctionStatus, ParticipationBond, PlatformConfig};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct PostBond<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + ParticipationBond::INIT_SPACE,
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bond: Box<Account<'info, ParticipationBond>>,
+
+    #[account(
+        seeds = [b"config", platform_config.admin.as_ref()],
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // one bond vault per auction, owned by the auction PDA
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = token_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostBond<'info> {
+    pub fn post_bond(&mut self, bumps: &PostBondBumps, hook_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let amount = self
+            .auction
+            .participation_bond
+            .ok_or(BidError::NoParticipationBond)?;
+
+        // Validations
+        require!(
+            matches!(
+                self.auction.auction_status,
+                AuctionStatus::Pending | AuctionStatus::Active | AuctionStatus::Paused
+            ),
+            BidError::AuctionNotAvailable
+        );
+        require!(
+            self.token_mint.key() == self.auction.accepted_token,
+            BidError::WrongToken
+        );
+
+        extensions::transfer_checked(
+            &self.token_program.to_account_info(),
+            self.bidder_token_account.to_account_info(),
+            self.token_mint.to_account_info(),
+            self.bond_vault.to_account_info(),
+            self.bidder.to_account_info(),
+            hook_accounts,
+            // the bidder covers any transfer fee so the vault holds the full bond
+            extensions::gross_up(&self.token_mint.to_account_info(), amount)?,
+            self.token_mint.decimals,
+            &[],
+        )?;
+
+        self.bond.set_inner(ParticipationBond {
+            bidder: self.bidder.key(),
+            auction: self.auction.key(),
+            amount,
+            bump: bumps.bond,
+        });
+
+        emit!(
+            BondPosted {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                amount,
+            }
+        );
+
+        Ok(())
+    }
+}
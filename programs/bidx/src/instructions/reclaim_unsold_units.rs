@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::AuctionError;
+use crate::events::UnsoldUnitsReclaimed;
+use crate::extensions;
+use crate::states::{Auction, AuctionStatus, UnitBook};
+
+/// Returns to the seller the units of a multi-unit auction that no winner can
+/// settle: units nobody won, and units won below the reserve.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ReclaimUnsoldUnits<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller,
+        has_one = item_vault
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    /// CHECK: the auction's unit book, empty if nobody bid
+    #[account(
+        seeds = [b"unit_book", auction.key().as_ref()],
+        bump
+    )]
+    pub unit_book: UncheckedAccount<'info>,
+
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = nft_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub item_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = seller,
+    )]
+    pub seller_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ReclaimUnsoldUnits<'info> {
+    /// `hook_accounts` carries any accounts a transfer-hook item mint needs.
+    pub fn reclaim_unsold_units(&mut self, nonce: u64, hook_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let auction = &self.auction;
+        let multi_unit = auction.multi_unit.as_ref().ok_or(AuctionError::InvalidMultiUnit)?;
+
+        // units still owed to winners who can settle stay in the vault
+        let owed = match auction.auction_status {
+            AuctionStatus::Ended | AuctionStatus::Settled => {
+                require!(auction.hidden_reserve.is_none(), AuctionError::ReserveNotRevealed);
+                if self.unit_book.data_is_empty() {
+                    0
+                } else {
+                    let book = UnitBook::try_deserialize(&mut &self.unit_book.try_borrow_data()?[..])?;
+                    book.settleable_units(multi_unit, auction.reserved_price)
+                        .saturating_sub(auction.units_settled)
+                }
+            }
+            // nobody settles a failed auction
+            AuctionStatus::Failed => 0,
+            _ => return err!(AuctionError::AuctionNotEnded),
+        };
+        let units = self.item_vault.amount.saturating_sub(owed);
+        require!(units > 0, AuctionError::NoUnsoldUnits);
+
+        //PDA SEEDS
+        let seller_key = self.seller.key();
+        let auction_seeds = &[
+            b"auction",
+            seller_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[auction.bump]
+        ];
+        let auction_signer_seeds = &[&auction_seeds[..]];
+
+        extensions::transfer_checked(
+            &self.token_program.to_account_info(),
+            self.item_vault.to_account_info(),
+            self.nft_mint.to_account_info(),
+            self.seller_nft_account.to_account_info(),
+            auction.to_account_info(),
+            hook_accounts,
+            units,
+            self.nft_mint.decimals,
+            auction_signer_seeds,
+        )?;
+
+        emit!(
+            UnsoldUnitsReclaimed {
+                auction: auction.key(),
+                seller: seller_key,
+                units,
+            }
+        );
+
+        Ok(())
+    }
+}
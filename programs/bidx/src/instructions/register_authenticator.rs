@@ -33,7 +33,7 @@ impl<'info> RegisterAuthenticators<'info> {
             );
 
             require!(
-                !self.registry.authenticators.contains(&authenticator),
+                !self.registry.authenticators.contains(authenticator),
                 AuctionAuthError::AlreadyRegistered
             );
 
@@ -42,7 +42,7 @@ impl<'info> RegisterAuthenticators<'info> {
                 ConfigError::AdminCannotbeAuthenticator
             );
         
-            self.registry.authenticators.push(authenticator.clone());
+            self.registry.authenticators.push(*authenticator);
             
         }
 
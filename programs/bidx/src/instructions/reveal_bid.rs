@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    Mint, TokenAccount, TokenInterface,
+};
+
+use crate::events::BidRevealed;
+use crate::states::{Auction, AuctionStatus, AuctionType, Bid};
+use crate::errors::{AuctionError, BidError};
+use crate::extensions;
+
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct RevealBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    ///CHECK: seller account
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+
+    #[account(
+        mut,
+        seeds = [b"bid", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+        has_one = auction
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RevealBid<'info> {
+    pub fn reveal_bid(
+        &mut self,
+        amount: u64,
+        salt: [u8; 32],
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let AuctionType::SealedBid { reveal_end_date, .. } = self.auction.auction_type else {
+            return err!(BidError::NotSealedBid);
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            self.auction.auction_status == AuctionStatus::Revealing && now < reveal_end_date,
+            AuctionError::NotRevealing
+        );
+
+        require!(!self.bid.revealed, BidError::AlreadyRevealed);
+
+        let commitment = self.bid.commitment.ok_or(BidError::NotSealedBid)?;
+        require!(
+            Bid::sealed_commitment(amount, &salt, &self.bidder.key()) == commitment,
+            BidError::CommitmentMismatch
+        );
+        require!(amount <= self.bid.amount, BidError::DepositTooLow);
+        require!(amount >= self.auction.starting_bid, BidError::BidTooLow);
+
+        // Refund the deposit above the revealed bid so escrow holds exactly the bid
+        let excess = self.bid.amount - amount;
+        if excess > 0 {
+            let bidder_key = self.bidder.key();
+            let auction_key = self.auction.key();
+            let seeds = &[
+                b"bid",
+                bidder_key.as_ref(),
+                auction_key.as_ref(),
+                &[self.bid.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                self.escrow_vault.to_account_info(),
+                self.token_mint.to_account_info(),
+                self.bidder_token_account.to_account_info(),
+                self.bid.to_account_info(),
+                hook_accounts,
+                excess,
+                self.token_mint.decimals,
+                signer_seeds,
+            )?;
+        }
+
+        self.bid.amount = amount;
+        self.bid.revealed = true;
+        self.bid.time_stamp = now;
+
+        // Highest valid reveal wins; ties go to the earlier reveal
+        let is_highest = amount > self.auction.highest_bid;
+        if is_highest {
+            self.auction.second_highest_bid = self.auction.highest_bid;
+            self.auction.highest_bid = amount;
+            self.auction.highest_bidder = self.bidder.key();
+        } else if amount > self.auction.second_highest_bid {
+            self.auction.second_highest_bid = amount;
+        }
+
+        emit!(
+            BidRevealed {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                amount,
+                is_highest,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
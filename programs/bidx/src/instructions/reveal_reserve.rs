@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ReserveRevealed;
+use crate::states::{Auction, AuctionStatus};
+use crate::errors::AuctionError;
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct RevealReserve<'info> {
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Account<'info, Auction>,
+}
+
+impl<'info> RevealReserve<'info> {
+    pub fn reveal_reserve(&mut self, reserved_price: u64, salt: [u8; 32]) -> Result<()> {
+        let auction = &mut self.auction;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Validations
+        let hidden_reserve = auction
+            .hidden_reserve
+            .as_ref()
+            .ok_or(AuctionError::NoHiddenReserve)?;
+        require!(
+            auction.auction_status == AuctionStatus::Ended,
+            AuctionError::AuctionNotEnded
+        );
+        require!(
+            now <= hidden_reserve.reveal_deadline,
+            AuctionError::ReserveRevealExpired
+        );
+        require!(
+            Auction::reserve_commitment(reserved_price, &salt, &auction.key()) == hidden_reserve.commitment,
+            AuctionError::ReserveCommitmentMismatch
+        );
+        require!(
+            reserved_price > auction.starting_bid,
+            AuctionError::ReservedPriceTooLow
+        );
+
+        auction.reserved_price = reserved_price;
+        auction.hidden_reserve = None;
+
+        auction.lift_to_reserve();
+
+        // nothing can settle under the reserve, so every bidder is refunded
+        if auction.reserve_unmet() {
+            auction.auction_status = AuctionStatus::Failed;
+        }
+
+        emit!(
+            ReserveRevealed {
+                auction: auction.key(),
+                reserved_price,
+                timestamp: now,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -2,17 +2,20 @@ use anchor_lang::prelude::*;
 
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{
-        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
-        TransferChecked,
-    },
+    token_interface::{sync_native, Mint, SyncNative, TokenAccount, TokenInterface},
 };
 
 use crate::{
     AuctionAuthError,
-    errors::AuctionError,
-    events::AuctionSettled,
-    states::{AssetType, Auction, AuctionStatus, Authentication, Bid, PlatformConfig},
+    errors::{AuctionError, BidError, ConfigError},
+    events::{AuctionFailed, AuctionSettled, BondReleased},
+    bubblegum::{Leaf, LeafTransfer, COMPRESSED_ACCOUNTS},
+    extensions,
+    metaplex::{ItemTransfer, ProgrammableTransfer, PROGRAMMABLE_ACCOUNTS},
+    states::{
+        AssetType, Auction, AuctionStatus, Authentication, Bid, Escrow, ItemCustody, LeafProof, LotManifest, ParticipationBond,
+        PlatformConfig, UnitBook,
+    },
 };
 
 
@@ -53,12 +56,29 @@ pub struct SettleAuction<'info> {
     )]
     pub authentication: Option<Box<Account<'info, Authentication>>>,
 
+    #[account(
+        seeds = [b"unit_book", auction.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Option<Box<Account<'info, UnitBook>>>,
+
+    #[account(
+        seeds = [b"lot", auction.key().as_ref()],
+        bump = lot_manifest.bump
+    )]
+    pub lot_manifest: Option<Box<Account<'info, LotManifest>>>,
+
     #[account(
         seeds = [b"config", platform_config.admin.as_ref()],
-        bump = platform_config.bump
+        bump = platform_config.bump,
+        address = auction.platform_config @ ConfigError::WrongPlatformConfig,
+        constraint = !platform_config.is_paused @ ConfigError::PlatformPaused
     )]
     pub platform_config: Box<Account<'info, PlatformConfig>>,
 
+    /// CHECK: Pyth price update for USD-priced auctions, owner and layout checked in Auction::usd_clearing_price
+    pub price_update: Option<UncheckedAccount<'info>>,
+
     //escrow vault - where winner bid is stored
     #[account(
         mut,
@@ -66,16 +86,16 @@ pub struct SettleAuction<'info> {
         associated_token::authority = bid,
         associated_token::token_program = token_program
     )]
-    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    //seller's token account (receives funds here)
+    //seller's token account (receives funds here; native SOL goes to the seller wallet)
     #[account(
         mut,
-        constraint = seller_token_account.mint == token_mint.key() @ AuctionError::WrongToken,
+        token::mint = token_mint,
     )]
-    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub seller_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    //Platform treasury
+    //Platform treasury (treasury_sol, synced after lamports are credited, for native SOL)
     #[account(
         mut
     )]
@@ -89,8 +109,8 @@ pub struct SettleAuction<'info> {
     )]
     pub authenticator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    //NFT Accounts
-    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    //NFT Accounts (left out for compressed NFTs)
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
     #[account(
         mut,
@@ -98,53 +118,165 @@ pub struct SettleAuction<'info> {
         associated_token::authority = auction,
         associated_token::token_program = token_program,
     )]
-    pub item_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub item_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         mut,
         associated_token::mint = nft_mint,
         associated_token::authority = winner,
     )]
-    pub winner_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub winner_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
+    //winner's token account (receives the Vickrey refund, if any)
     #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program,
+    )]
+    pub winner_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", winner.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump,
+        close = winner
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
+
+    //auction's bond vault (returns the winner's participation bond)
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program,
+    )]
+    pub bond_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // writable so a transfer-fee mint can collect the escrow's withheld fees
+    #[account(
+        mut,
         mint::token_program = token_program
     )]
-    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>
 }
 
 impl<'info> SettleAuction <'info> {
-    pub fn settle_auction(&mut self, nonce: u64) -> Result<()> {
+    /// `remaining_accounts` carries a programmable NFT's transfer accounts
+    /// (see `ProgrammableTransfer::new`) or a compressed NFT's tree accounts
+    /// and `leaf_proof.proof_len` proof nodes (see `LeafTransfer::new`), then
+    /// the lot's (mint, vault, winner account) triples, then any accounts
+    /// transfer-hook mints need.
+    pub fn settle_auction(
+        &mut self,
+        nonce: u64,
+        leaf_proof: Option<LeafProof>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         let auction = &mut self.auction;
         let bid = &self.bid;
+        let custody_len = match &auction.item_custody {
+            ItemCustody::Token => 0,
+            ItemCustody::Programmable => PROGRAMMABLE_ACCOUNTS,
+            ItemCustody::Compressed { .. } => {
+                COMPRESSED_ACCOUNTS + leaf_proof.as_ref().map_or(0, |proof| proof.proof_len as usize)
+            }
+        };
+        let (custody_accounts, remaining_accounts) =
+            remaining_accounts.split_at(custody_len.min(remaining_accounts.len()));
+        let (lot_accounts, hook_accounts) = remaining_accounts
+            .split_at((auction.lot_items as usize * 3).min(remaining_accounts.len()));
 
         //validations
         require!(
             auction.auction_status == AuctionStatus::Ended,
             AuctionError::AuctionNotEnded
         );
-
         require!(
-            self.winner.key() == auction.highest_bidder,
-            AuctionError::NotWinner
+            auction.hidden_reserve.is_none(),
+            AuctionError::ReserveNotRevealed
         );
 
-        require!(
-            auction.highest_bid >= auction.reserved_price,
-            AuctionError::ReserveNotMet
-        );
+        // multi-unit winners are read off the unit book, everyone else must be the highest bidder
+        let (units, winning_bid, clearing_price) = match auction.multi_unit.as_ref() {
+            Some(multi_unit) => {
+                let book = self.unit_book.as_ref().ok_or(AuctionError::UnitBookRequired)?;
+                let (units, bid_unit_price) = book
+                    .allocation(&self.winner.key(), multi_unit.quantity)
+                    .ok_or(AuctionError::NotWinner)?;
+                let unit_price = book.unit_price(&multi_unit.pricing, bid_unit_price);
+
+                require!(
+                    unit_price >= auction.reserved_price,
+                    AuctionError::ReserveNotMet
+                );
+
+                (units, bid_unit_price * units, unit_price * units)
+            }
+            None => {
+                require!(
+                    self.winner.key() == auction.highest_bidder,
+                    AuctionError::NotWinner
+                );
+
+                // a USD reserve is checked below, once the oracle prices the bid
+                require!(
+                    auction.usd_pricing.is_some() || auction.highest_bid >= auction.reserved_price,
+                    AuctionError::ReserveNotMet
+                );
+
+                (1, auction.highest_bid, auction.clearing_price())
+            }
+        };
 
         require!(
             bid.is_active,
             AuctionError::BidNotActive
         );
 
+        let escrow = Escrow::new(
+            &bid.token_mint,
+            self.escrow_vault.as_deref(),
+            self.token_mint.as_deref(),
+            &self.token_program.to_account_info(),
+            hook_accounts,
+        )?;
+
+        // a maximum that covers the USD reserve bids at least the reserve
+        let clearing_price = if auction.usd_pricing.is_some() {
+            let now = Clock::get()?.unix_timestamp;
+            match auction.usd_clearing_price(self.price_update.as_deref(), escrow.decimals(), now)? {
+                Some(price) => price,
+                // nothing can settle under the reserve, so every bidder is refunded
+                None => {
+                    auction.auction_status = AuctionStatus::Failed;
+                    emit!(
+                        AuctionFailed {
+                            auction: auction.key(),
+                            highest_bid: auction.highest_bid,
+                            timestamp: now,
+                        }
+                    );
+                    return Ok(());
+                }
+            }
+        } else {
+            clearing_price
+        };
+
+        // lamport fees are wrapped into the wSOL treasury
         require!(
-            self.treasury.key() == self.platform_config.treasury_usdc
-                || self.treasury.key() == self.platform_config.treasury_sol,
+            match escrow {
+                Escrow::Token { .. } => self.platform_config.is_fee_treasury(
+                    &self.platform_config.key(),
+                    &self.treasury,
+                    &bid.token_mint,
+                ),
+                Escrow::Lamports => self.treasury.key() == self.platform_config.treasury_sol,
+            },
             AuctionError::InvalidTreasury
         );
 
@@ -157,36 +289,42 @@ impl<'info> SettleAuction <'info> {
 
         if let Some(auth_token_account) = self.authenticator_token_account.as_ref() {
             require!(
-                auth_token_account.mint == self.token_mint.key(),
+                auth_token_account.mint == bid.token_mint,
                 AuctionError::WrongToken
             );
         }
 
-        let winning_bid = auction.highest_bid;
+        // the clearing price is in base units, the winner pays it in their escrowed mint
+        let payment = auction.from_base(&bid.token_mint, clearing_price)?;
+
         let plaform_fee_bps = self.platform_config.platform_fee_bps;
 
-        //calculate fees
-        let platform_fee = (winning_bid as u128)
-            .checked_mul(plaform_fee_bps as u128)
-            .unwrap()
-            .checked_div(10_000)
-            .unwrap() as u64;
+        //calculate fees (grossed up so transfer fees come off the seller's share, not the fees)
+        let platform_fee = escrow.gross_up(
+            (payment as u128)
+                .checked_mul(plaform_fee_bps as u128)
+                .unwrap()
+                .checked_div(10_000)
+                .unwrap() as u64,
+        )?;
 
         // seller amount
-        let mut seller_amount = winning_bid - platform_fee;
+        let mut seller_amount = payment - platform_fee;
         //auth fee
         let mut auth_fee: u64 = 0;
 
         //if physical asset; deduct auth fee
         if auction.asset_type == AssetType::PhysicalRWA {
             let auth_fee_bps = self.platform_config.auth_fee_bps;
-            auth_fee = (winning_bid as u128)
-                .checked_mul(auth_fee_bps as u128)
-                .unwrap()
-                .checked_div(10_000)
-                .unwrap() as u64;
-
-            seller_amount = seller_amount - auth_fee;
+            auth_fee = escrow.gross_up(
+                (payment as u128)
+                    .checked_mul(auth_fee_bps as u128)
+                    .unwrap()
+                    .checked_div(10_000)
+                    .unwrap() as u64,
+            )?;
+
+            seller_amount -= auth_fee;
         }
 
         //PDA SEEDS
@@ -211,101 +349,211 @@ impl<'info> SettleAuction <'info> {
 
         let bid_signer_seeds = &[&bid_seeds[..]]; //bid_signer
 
+        let bid_info = self.bid.to_account_info();
+
         // Settle Seller
-        transfer_checked(
-            CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                TransferChecked{
-                    from: self.escrow_vault.to_account_info(),
-                    to: self.seller_token_account.to_account_info(),
-                    mint: self.token_mint.to_account_info(),
-                    authority: self.bid.to_account_info(),
-                },
-                bid_signer_seeds
-            ),
+        escrow.pay(
+            &bid_info,
+            self.seller_token_account.as_ref().map(|account| account.to_account_info()),
+            &self.seller.to_account_info(),
             seller_amount,
-            self.token_mint.decimals
+            bid_signer_seeds,
         )?;
 
         //settle platform (pay fee to treasury)
-        transfer_checked(
-            CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-                TransferChecked{
-                    from: self.escrow_vault.to_account_info(),
-                    to: self.treasury.to_account_info(),
-                    mint: self.token_mint.to_account_info(),
-                    authority: self.bid.to_account_info(),
-                },
-                bid_signer_seeds
-            ),
+        escrow.pay(
+            &bid_info,
+            Some(self.treasury.to_account_info()),
+            &self.treasury.to_account_info(),
             platform_fee,
-            self.token_mint.decimals
+            bid_signer_seeds,
         )?;
+        if let Escrow::Lamports = escrow {
+            sync_native(CpiContext::new(
+                self.token_program.to_account_info(),
+                SyncNative {
+                    account: self.treasury.to_account_info(),
+                },
+            ))?;
+        }
 
         // settle authenticator (if Physical Real World Asset)
         if auction.asset_type == AssetType::PhysicalRWA {
-            if let Some(auth_token_account) = self.authenticator_token_account.as_ref() {
-
-                
-                transfer_checked(
-                    CpiContext::new_with_signer(
-                        self.token_program.to_account_info(),
-                        TransferChecked{
-                            from: self.escrow_vault.to_account_info(),
-                            to: auth_token_account.to_account_info(),
-                            mint: self.token_mint.to_account_info(),
-                            authority: self.bid.to_account_info()
-                        },
-                        bid_signer_seeds
-                    ),
-                    auth_fee,
-                    self.token_mint.decimals
-                )?;
+            match escrow {
+                Escrow::Token { .. } => {
+                    if let Some(auth_token_account) = self.authenticator_token_account.as_ref() {
+                        escrow.pay(
+                            &bid_info,
+                            Some(auth_token_account.to_account_info()),
+                            &self.authenticator.to_account_info(),
+                            auth_fee,
+                            bid_signer_seeds,
+                        )?;
+                    }
+                }
+                // lamports can only go to the authenticator assigned to this auction
+                Escrow::Lamports => {
+                    require!(
+                        self.authentication
+                            .as_ref()
+                            .is_some_and(|auth| auth.authenticator == self.authenticator.key()),
+                        AuctionAuthError::InvalidAuthentication
+                    );
+                    escrow.pay(
+                        &bid_info,
+                        None,
+                        &self.authenticator.to_account_info(),
+                        auth_fee,
+                        bid_signer_seeds,
+                    )?;
+                }
             }
 
             //update auth fee as paid
             if let Some(auth) = &mut self.authentication {
                 auth.fee_amount = auth_fee;
                 auth.fee_paid = true;
-            } else {}
+            }
         };
 
         // Transfer NFT to winner
-        transfer_checked(
-            CpiContext::new_with_signer(
+        match (
+            &auction.item_custody,
+            self.nft_mint.as_deref(),
+            self.item_vault.as_deref(),
+            self.winner_nft_account.as_deref(),
+        ) {
+            (ItemCustody::Token, Some(nft_mint), Some(item_vault), Some(winner_nft_account)) => extensions::transfer_checked(
+                &self.token_program.to_account_info(),
+                item_vault.to_account_info(),
+                nft_mint.to_account_info(),
+                winner_nft_account.to_account_info(),
+                auction.to_account_info(),
+                hook_accounts,
+                units,
+                nft_mint.decimals,
+                auction_signer_seeds,
+            )?,
+            (ItemCustody::Programmable, Some(nft_mint), Some(item_vault), Some(winner_nft_account)) => {
+                ProgrammableTransfer::new(custody_accounts, &nft_mint.key())?.transfer(
+                    ItemTransfer {
+                        from: item_vault.to_account_info(),
+                        owner: auction.to_account_info(),
+                        to: winner_nft_account.to_account_info(),
+                        to_owner: self.winner.to_account_info(),
+                        mint: nft_mint.to_account_info(),
+                        payer: self.winner.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    units,
+                    auction_signer_seeds,
+                )?
+            }
+            (ItemCustody::Compressed { tree, leaf_index, data_hash, creator_hash }, ..) => {
+                let leaf_proof = leaf_proof.as_ref().ok_or(AuctionError::CompressedAccountsRequired)?;
+                LeafTransfer::new(custody_accounts, tree, leaf_proof.proof_len.into())?.transfer(
+                    auction.to_account_info(),
+                    self.winner.to_account_info(),
+                    self.system_program.to_account_info(),
+                    &Leaf {
+                        root: leaf_proof.root,
+                        data_hash: *data_hash,
+                        creator_hash: *creator_hash,
+                        leaf_index: *leaf_index,
+                    },
+                    auction_signer_seeds,
+                )?
+            }
+            _ => return err!(AuctionError::ItemAccountsRequired),
+        }
+
+        // Transfer the rest of the lot: remaining accounts are (mint, vault, winner account) per item
+        if auction.lot_items > 0 {
+            self.lot_manifest
+                .as_ref()
+                .ok_or(AuctionError::LotAccountsRequired)?
+                .transfer_items(
+                    lot_accounts,
+                    hook_accounts,
+                    &self.winner.key(),
+                    self.token_program.to_account_info(),
+                    auction.to_account_info(),
+                    auction_signer_seeds,
+                )?;
+        }
+
+        // refund the winner whatever escrow holds above the clearing price
+        let refund = bid.amount - payment;
+        if refund > 0 {
+            let winner_token_account = match escrow {
+                Escrow::Token { .. } => Some(
+                    self.winner_token_account
+                        .as_ref()
+                        .ok_or(AuctionError::WinnerAccountRequired)?
+                        .to_account_info(),
+                ),
+                Escrow::Lamports => None,
+            };
+
+            escrow.pay(
+                &bid_info,
+                winner_token_account,
+                &self.winner.to_account_info(),
+                refund,
+                bid_signer_seeds,
+            )?;
+        }
+
+        // the winner's participation bond is released once the lot is paid for
+        if let Some(bond) = self.bond.as_ref() {
+            let bond_vault = self.bond_vault.as_ref().ok_or(BidError::BondVaultRequired)?;
+            let (Some(winner_token_account), Some(token_mint)) =
+                (self.winner_token_account.as_ref(), self.token_mint.as_ref())
+            else {
+                return err!(AuctionError::WinnerAccountRequired);
+            };
+
+            bond.pay_out(
+                auction,
+                bond_vault,
+                winner_token_account.to_account_info(),
+                token_mint,
                 self.token_program.to_account_info(),
-                TransferChecked{
-                    from: self.item_vault.to_account_info(),
-                    to: self.winner_nft_account.to_account_info(),
-                    mint: self.nft_mint.to_account_info(),
-                    authority: auction.to_account_info()
-                },
-                auction_signer_seeds
-            ),
-            1,
-            self.nft_mint.decimals,
-        )?;
+                hook_accounts,
+                auction_signer_seeds,
+            )?;
+
+            emit!(BondReleased {
+                auction: auction.key(),
+                bidder: self.winner.key(),
+                amount: bond.amount,
+            });
+        }
 
         //close escrow vault for rent reclaim
-        let close_acct_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            CloseAccount {
-                account: self.escrow_vault.to_account_info(),
-                destination: self.winner.to_account_info(),
-                authority: self.bid.to_account_info()
-            }, 
-            bid_signer_seeds
-        );
-        close_account(close_acct_ctx)?;
+        escrow.close(&bid_info, &self.winner.to_account_info(), bid_signer_seeds)?;
+
+        //update auction status (multi-unit auctions settle once every winner has)
+        auction.units_settled += units;
+        let fully_settled = match (auction.multi_unit.as_ref(), self.unit_book.as_ref()) {
+            (Some(multi_unit), Some(book)) => {
+                auction.units_settled >= book.settleable_units(multi_unit, auction.reserved_price)
+            }
+            _ => true,
+        };
+        if fully_settled {
+            auction.auction_status = AuctionStatus::Settled;
+        }
 
-        //update auction status
-        auction.auction_status = AuctionStatus::Settled;
+        self.bid.is_active = false;
+        self.bid.is_winner = true;
 
         emit!(AuctionSettled {
             auction: auction.key(),
             winner: self.winner.key(),
-            final_price: winning_bid,
+            winning_bid,
+            clearing_price,
+            units,
             platform_fee,
             auth_fee,
             seller_amount,
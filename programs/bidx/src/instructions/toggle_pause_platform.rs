@@ -22,6 +22,9 @@ pub struct TogglePause <'info> {
 
 
 impl<'info> TogglePause <'info> {
+    /// While paused the platform takes no new auctions, bids or settlements.
+    /// Exit paths stay open so nobody's funds or items are trapped: bid
+    /// withdrawals, sealed-bid reveals, reserve reveals, ending and cancelling.
     pub fn toggle_pause (&mut self) -> Result<()> {
         let paused = self.platform_config.is_paused;
         self.platform_config.is_paused = !paused;
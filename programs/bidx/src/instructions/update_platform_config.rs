@@ -24,11 +24,13 @@ impl <'info> UpdatePlatformConfig<'info> {
         auth_fee_bps: Option<u16>,
         min_auction_duration: Option<i64>,
         max_auction_duration: Option<i64>,
+        cancellation_fee_bps: Option<u16>,
     ) -> Result<()>{
         let new_fee_bps = platform_fee_bps.unwrap_or(self.platform_config.platform_fee_bps);
         let new_auth_fee_bps = auth_fee_bps.unwrap_or(self.platform_config.auth_fee_bps);
         let new_min_duration = min_auction_duration.unwrap_or(self.platform_config.min_auction_duration);
         let new_max_duration = max_auction_duration.unwrap_or(self.platform_config.max_auction_duration);
+        let new_cancellation_fee_bps = cancellation_fee_bps.unwrap_or(self.platform_config.cancellation_fee_bps);
 
         require!(
             new_fee_bps > 0,
@@ -40,13 +42,18 @@ impl <'info> UpdatePlatformConfig<'info> {
             ConfigError::FeeTooLow
         );
 
+        require!(
+            new_cancellation_fee_bps <= 1_000,
+            ConfigError::FeeTooHigh
+        );
+
         require!(
             new_min_duration > 0,
             ConfigError::DurationNotRealistic
         );
 
         require!(
-            max_auction_duration > min_auction_duration,
+            new_max_duration > new_min_duration,
             ConfigError::DurationNotRealistic
         );
 
@@ -68,6 +75,10 @@ impl <'info> UpdatePlatformConfig<'info> {
             self.platform_config.max_auction_duration = new_max_duration;
             updated_fields.push(String::from("max_auction_duration"));
         };
+        if new_cancellation_fee_bps != self.platform_config.cancellation_fee_bps {
+            self.platform_config.cancellation_fee_bps = new_cancellation_fee_bps;
+            updated_fields.push(String::from("cancellation_fee_bps"));
+        };
 
         emit!(
             PlatformConfigUpdated {
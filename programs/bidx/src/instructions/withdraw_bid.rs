@@ -1,14 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    token_interface::{
-        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
-        TransferChecked,
-    },
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::events::BidWithdrawn;
-use crate::states::{Auction, AuctionStatus, Bid};
-use crate::errors::BidError;
+use crate::events::{BidWithdrawn, BondReleased};
+use crate::states::{Auction, AuctionStatus, AuctionType, Bid, Escrow, ParticipationBond, UnitBook};
+use crate::errors::{AuctionError, BidError};
 
 
 #[derive(Accounts)]
@@ -17,7 +12,8 @@ pub struct WithdrawBid <'info> {
     #[account(mut)]
     pub bidder: Signer<'info>,
 
-    ///CHECK: seller account 
+    ///CHECK: seller account (collects native SOL sealed-bid penalties)
+    #[account(mut)]
     pub seller: UncheckedAccount<'info>,
 
     #[account(
@@ -37,36 +33,94 @@ pub struct WithdrawBid <'info> {
     )]
     pub bid: Account<'info, Bid>,
 
+    // token accounts are left out of native SOL auctions, whose escrow is the bid PDA
     #[account(
         mut,
         associated_token::mint = token_mint,
         associated_token::authority = bid,
     )]
-    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         mut,
         associated_token::mint = token_mint,
         associated_token::authority = bidder,
     )]
-    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub bidder_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [b"unit_book", auction.key().as_ref()],
+        bump = unit_book.bump
+    )]
+    pub unit_book: Option<Account<'info, UnitBook>>,
+
+    //seller's token account (collects sealed-bid penalties)
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = seller,
+    )]
+    pub seller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub bond: Option<Box<Account<'info, ParticipationBond>>>,
 
+    //auction's bond vault (returns the participation bond)
     #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = auction,
+    )]
+    pub bond_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // writable so a transfer-fee mint can collect the escrow's withheld fees
+    #[account(
+        mut,
         mint::token_program = token_program
     )]
-    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>
 }
 
 impl <'info> WithdrawBid <'info> {
-    pub fn withdraw_bid(&mut self) -> Result<()> {
+    pub fn withdraw_bid(&mut self, nonce: u64, hook_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // A hidden reserve left unrevealed past its deadline, or a reserve the
+        // highest bid never reached, fails the auction
+        let now = Clock::get()?.unix_timestamp;
+        if self.auction.has_failed(now) {
+            self.auction.auction_status = AuctionStatus::Failed;
+        }
+
         let bid = &self.bid;
         let auction = &self.auction;
-
+        let void = auction.auction_status == AuctionStatus::Failed
+            || auction.auction_status == AuctionStatus::Cancelled;
+
+        // multi-unit winners are read off the unit book, everyone else is the highest bidder;
+        // a multi-unit winner priced under the reserve can't settle, so is refunded instead
+        let is_winner = match (auction.multi_unit.as_ref(), self.unit_book.as_ref()) {
+            (Some(multi_unit), Some(book)) => book
+                .allocation(&self.bidder.key(), multi_unit.quantity)
+                .is_some_and(|(_, bid_unit_price)| {
+                    book.unit_price(&multi_unit.pricing, bid_unit_price) >= auction.reserved_price
+                }),
+            (Some(_), None) => return err!(AuctionError::UnitBookRequired),
+            (None, _) => auction.highest_bidder == self.bidder.key(),
+        };
+
+        // everyone, winner included, is refunded in full from a failed or cancelled auction
         require!(
-            self.auction.auction_status == AuctionStatus::Ended
-            && self.auction.highest_bidder != self.bidder.key(),
+            void
+                || ((auction.auction_status == AuctionStatus::Ended
+                    || auction.auction_status == AuctionStatus::Settled)
+                    && !is_winner),
             BidError::StillWinning
         );
 
@@ -83,41 +137,100 @@ impl <'info> WithdrawBid <'info> {
 
         let signer_seeds = &[&seeds[..]];
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            TransferChecked {
-                from: self.escrow_vault.to_account_info(),
-                to: self.bidder_token_account.to_account_info(),
-                mint: self.token_mint.to_account_info(),
-                authority: self.bid.to_account_info()
-            },
-            signer_seeds
-        );
-
-        transfer_checked(
-            cpi_ctx,
-            self.bid.amount,
-            self.token_mint.decimals
+        // Unrevealed sealed commitments forfeit a share of the deposit to the seller
+        let penalty = match auction.auction_type {
+            AuctionType::SealedBid { penalty_bps, .. } if !bid.revealed && !void => {
+                (bid.amount as u128)
+                    .checked_mul(penalty_bps as u128)
+                    .unwrap()
+                    .checked_div(10_000)
+                    .unwrap() as u64
+            }
+            _ => 0,
+        };
+        let refund = bid.amount - penalty;
+
+        let escrow = Escrow::new(
+            &bid.token_mint,
+            self.escrow_vault.as_deref(),
+            self.token_mint.as_deref(),
+            &self.token_program.to_account_info(),
+            hook_accounts,
+        )?;
+        let bid_info = self.bid.to_account_info();
+
+        if penalty > 0 {
+            let seller_token_account = match escrow {
+                Escrow::Token { .. } => Some(
+                    self.seller_token_account
+                        .as_ref()
+                        .ok_or(BidError::SellerAccountRequired)?
+                        .to_account_info(),
+                ),
+                Escrow::Lamports => None,
+            };
+
+            escrow.pay(
+                &bid_info,
+                seller_token_account,
+                &self.seller.to_account_info(),
+                penalty,
+                signer_seeds,
+            )?;
+        }
+
+        escrow.pay(
+            &bid_info,
+            self.bidder_token_account.as_ref().map(|account| account.to_account_info()),
+            &self.bidder.to_account_info(),
+            refund,
+            signer_seeds,
         )?;
 
         //Close escrow account
-        close_account(
-            CpiContext::new_with_signer(
+        escrow.close(&bid_info, &self.bidder.to_account_info(), signer_seeds)?;
+
+        // the participation bond comes back with the bid
+        if let Some(bond) = self.bond.as_ref() {
+            let bond_vault = self.bond_vault.as_ref().ok_or(BidError::BondVaultRequired)?;
+            let (Some(bidder_token_account), Some(token_mint)) =
+                (self.bidder_token_account.as_ref(), self.token_mint.as_ref())
+            else {
+                return err!(BidError::TokenAccountsRequired);
+            };
+            let seller_key = self.seller.key();
+            let auction_seeds = &[
+                b"auction",
+                seller_key.as_ref(),
+                &nonce.to_le_bytes(),
+                &[self.auction.bump]
+            ];
+
+            bond.pay_out(
+                &self.auction,
+                bond_vault,
+                bidder_token_account.to_account_info(),
+                token_mint,
                 self.token_program.to_account_info(),
-                CloseAccount {
-                    account: self.escrow_vault.to_account_info(),
-                    destination: self.bidder.to_account_info(),
-                    authority: self.bid.to_account_info(),
-                },
-                signer_seeds
-            )
-        )?;
+                hook_accounts,
+                &[&auction_seeds[..]],
+            )?;
+
+            emit!(
+                BondReleased {
+                    auction: self.auction.key(),
+                    bidder: self.bidder.key(),
+                    amount: bond.amount,
+                }
+            );
+        }
 
         emit!(
             BidWithdrawn {
                 auction: self.auction.key(),
                 bidder: self.bidder.key(),
-                ammount: self.bid.amount
+                ammount: refund,
+                penalty,
             }
         );
 
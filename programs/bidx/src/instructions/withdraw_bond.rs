@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::BidError;
+use crate::events::BondReleased;
+use crate::states::{Auction, AuctionStatus, Bid, ParticipationBond};
+
+/// Returns the bond of a wallet whose bid is already withdrawn or settled, or
+/// that never bid. Open bids release their bond through withdraw_bid / settle.
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct WithdrawBond<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// CHECK: seller account for PDA seeds
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"auction", seller.key().as_ref(), &nonce.to_le_bytes()],
+        bump = auction.bump,
+        has_one = seller
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+
+    #[account(
+        mut,
+        seeds = [b"bond", bidder.key().as_ref(), auction.key().as_ref()],
+        bump = bond.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub bond: Box<Account<'info, ParticipationBond>>,
+
+    /// CHECK: the bidder's bid PDA, which must be closed or settled
+    #[account(
+        seeds = [b"bid", bidder.key().as_ref(), auction.key().as_ref()],
+        bump
+    )]
+    pub bid: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawBond<'info> {
+    pub fn withdraw_bond(&mut self, nonce: u64, hook_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        // Validations
+        require!(
+            matches!(
+                self.auction.auction_status,
+                AuctionStatus::Ended
+                    | AuctionStatus::Settled
+                    | AuctionStatus::Failed
+                    | AuctionStatus::Cancelled
+            ),
+            BidError::BondLocked
+        );
+        if !self.bid.data_is_empty() {
+            let bid = Bid::try_deserialize(&mut &self.bid.try_borrow_data()?[..])?;
+            require!(!bid.is_active, BidError::BondLocked);
+        }
+
+        //PDA signer
+        let seller_key = self.seller.key();
+        let auction_seeds = &[
+            b"auction",
+            seller_key.as_ref(),
+            &nonce.to_le_bytes(),
+            &[self.auction.bump]
+        ];
+        let auction_signer_seeds = &[&auction_seeds[..]];
+
+        self.bond.pay_out(
+            &self.auction,
+            &self.bond_vault,
+            self.bidder_token_account.to_account_info(),
+            &self.token_mint,
+            self.token_program.to_account_info(),
+            hook_accounts,
+            auction_signer_seeds,
+        )?;
+
+        emit!(
+            BondReleased {
+                auction: self.auction.key(),
+                bidder: self.bidder.key(),
+                amount: self.bond.amount,
+            }
+        );
+
+        Ok(())
+    }
+}
@@ -4,6 +4,11 @@ pub mod instructions;
 pub mod states;
 pub mod errors;
 pub mod events;
+pub mod bubblegum;
+pub mod extensions;
+pub mod merkle;
+pub mod metaplex;
+pub mod pyth;
 
 pub use instructions::*;
 pub use states::*;
@@ -37,8 +42,9 @@ pub mod bidx {
         )
     }
 
-    pub fn create_auction(
-        ctx: Context<CreateAuction>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateAuction<'info>>,
         accepted_token: Pubkey,
         starting_bid: u64,
         reserved_price: u64,
@@ -46,6 +52,9 @@ pub mod bidx {
         end_date: i64,
         document_hash: Option<String>,
         asset_type: AssetType,
+        auction_type: AuctionType,
+        terms: AuctionTerms,
+        compressed_leaf: Option<CompressedLeaf>,
     ) -> Result<()> {
         ctx.accounts.create(
             accepted_token,
@@ -56,11 +65,112 @@ pub mod bidx {
             asset_type,
             &ctx.bumps,
             document_hash,
+            auction_type,
+            terms,
+            compressed_leaf,
+            ctx.remaining_accounts,
         )
     }
 
-    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
-        ctx.accounts.place_bid(amount, &ctx.bumps)
+    pub fn place_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .place_bid(amount, &allowlist_proof, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn place_proxy_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
+        amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .place_proxy_bid(amount, &allowlist_proof, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn bid_units<'info>(
+        ctx: Context<'_, '_, '_, 'info, BidUnits<'info>>,
+        unit_price: u64,
+        quantity: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .bid_units(unit_price, quantity, &allowlist_proof, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn buy_now<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyNow<'info>>,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.buy_now(&allowlist_proof, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn commit_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, CommitBid<'info>>,
+        commitment: [u8; 32],
+        deposit: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .commit_bid(commitment, deposit, &allowlist_proof, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn reveal_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, RevealBid<'info>>,
+        nonce: u64,
+        amount: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.reveal_bid(amount, salt, ctx.remaining_accounts)
+    }
+
+    pub fn reveal_reserve(
+        ctx: Context<RevealReserve>,
+        nonce: u64,
+        reserved_price: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.reveal_reserve(reserved_price, salt)
+    }
+
+    pub fn cancel_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelAuction<'info>>,
+        nonce: u64,
+        leaf_proof: Option<LeafProof>,
+    ) -> Result<()> {
+        ctx.accounts.cancel_auction(nonce, leaf_proof, ctx.remaining_accounts)
+    }
+
+    pub fn activate_auction(ctx: Context<ActivateAuction>, nonce: u64) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.activate_auction()
+    }
+
+    pub fn pause_auction(ctx: Context<PauseAuction>, nonce: u64) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.pause_auction()
+    }
+
+    pub fn resume_auction(ctx: Context<PauseAuction>, nonce: u64) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.resume_auction()
+    }
+
+    pub fn amend_auction(
+        ctx: Context<AmendAuction>,
+        nonce: u64,
+        starting_bid: Option<u64>,
+        reserved_price: Option<u64>,
+        end_date: Option<i64>,
+        buy_now_price: Option<u64>,
+    ) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts
+            .amend_auction(starting_bid, reserved_price, end_date, buy_now_price)
     }
 
     pub fn end_auction(ctx: Context<EndAuction>, nonce: u64) -> Result<()> {
@@ -68,6 +178,11 @@ pub mod bidx {
         ctx.accounts.end_auction()
     }
 
+    pub fn fail_auction(ctx: Context<FailAuction>, nonce: u64) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.fail_auction()
+    }
+
     pub fn register_authenticators(
         ctx: Context<RegisterAuthenticators>,
         authenticators: Vec<Pubkey>,
@@ -95,30 +210,72 @@ pub mod bidx {
         ctx.accounts.attest_authentication(approved)
     }
 
-    pub fn settle_auction(ctx: Context<SettleAuction>, nonce:u64) -> Result<()> {
-        ctx.accounts.settle_auction(nonce)
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+        nonce: u64,
+        leaf_proof: Option<LeafProof>,
+    ) -> Result<()> {
+        ctx.accounts.settle_auction(nonce, leaf_proof, ctx.remaining_accounts)
     }
 
-    pub fn withdraw_bid(ctx: Context<WithdrawBid>, nonce: u64) -> Result<()> { 
-        let _ = nonce;
-        ctx.accounts.withdraw_bid()
+    pub fn reclaim_unsold_units<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReclaimUnsoldUnits<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.reclaim_unsold_units(nonce, ctx.remaining_accounts)
+    }
+
+    pub fn post_bond<'info>(
+        ctx: Context<'_, '_, '_, 'info, PostBond<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        let _ = nonce; // nonce used in account constraints
+        ctx.accounts.post_bond(&ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_bond<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawBond<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_bond(nonce, ctx.remaining_accounts)
+    }
+
+    pub fn forfeit_bond<'info>(
+        ctx: Context<'_, '_, '_, 'info, ForfeitBond<'info>>,
+        nonce: u64,
+        reason: ForfeitReason,
+    ) -> Result<()> {
+        ctx.accounts.forfeit_bond(nonce, reason, ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawBid<'info>>,
+        nonce: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_bid(nonce, ctx.remaining_accounts)
     }
 
     pub fn toggle_pause_platform(ctx: Context<TogglePause>) -> Result<()>{
         ctx.accounts.toggle_pause()
     }
 
+    pub fn open_treasury_vault(ctx: Context<OpenTreasuryVault>) -> Result<()> {
+        ctx.accounts.open_treasury_vault()
+    }
+
     pub fn update_platform_config(ctx: Context<UpdatePlatformConfig>,
         platform_fee_bps: Option<u16>,
         auth_fee_bps: Option<u16>,
         min_auction_duration: Option<i64>,
-        max_auction_duration: Option<i64>
+        max_auction_duration: Option<i64>,
+        cancellation_fee_bps: Option<u16>,
      ) -> Result<()> {
         ctx.accounts.update_platform_config(
             platform_fee_bps,
             auth_fee_bps,
             min_auction_duration,
-            max_auction_duration
+            max_auction_duration,
+            cancellation_fee_bps,
         )
     }
 
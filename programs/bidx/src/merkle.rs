@@ -0,0 +1,116 @@
+//! Merkle allowlists of bidder pubkeys.
+//!
+//! Leaves are `sha256(0x00 || pubkey)` and parents `sha256(0x01 || lo || hi)`
+//! with the two children sorted, so proofs need no left/right flags. A node
+//! without a sibling is carried up unchanged. `build_root` and `build_proof`
+//! are the off-chain companions of the on-chain `verify`, left out of the
+//! program build.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, bidder.as_ref()]).to_bytes()
+}
+
+fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+/// Whether `proof` links `bidder` to `root`.
+pub fn verify(root: &[u8; 32], bidder: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf(bidder), |node, sibling| parent(&node, sibling));
+    computed == *root
+}
+
+/// Every level of the tree, leaves first.
+#[cfg(not(target_os = "solana"))]
+fn levels(bidders: &[Pubkey]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![bidders.iter().map(leaf).collect::<Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => parent(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Root over `bidders`, or None for an empty list.
+#[cfg(not(target_os = "solana"))]
+pub fn build_root(bidders: &[Pubkey]) -> Option<[u8; 32]> {
+    levels(bidders).last().unwrap().first().copied()
+}
+
+/// Proof for `bidder`, or None if it isn't in `bidders`.
+#[cfg(not(target_os = "solana"))]
+pub fn build_proof(bidders: &[Pubkey], bidder: &Pubkey) -> Option<Vec<[u8; 32]>> {
+    let mut index = bidders.iter().position(|key| key == bidder)?;
+    let mut proof = vec![];
+    for level in levels(bidders).iter().take_while(|level| level.len() > 1) {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        index /= 2;
+    }
+    Some(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bidders(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn proofs_verify_against_the_root() {
+        // odd counts leave a node without a sibling on some level
+        for count in 1..=9 {
+            let bidders = bidders(count);
+            let root = build_root(&bidders).unwrap();
+            for bidder in &bidders {
+                let proof = build_proof(&bidders, bidder).unwrap();
+                assert!(verify(&root, bidder, &proof), "{count} bidders");
+            }
+        }
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let bidders = bidders(1);
+        assert_eq!(build_root(&bidders), Some(leaf(&bidders[0])));
+        assert_eq!(build_proof(&bidders, &bidders[0]), Some(vec![]));
+    }
+
+    #[test]
+    fn rejects_outsiders_and_foreign_proofs() {
+        let bidders = bidders(5);
+        let root = build_root(&bidders).unwrap();
+        let outsider = Pubkey::new_unique();
+        assert_eq!(build_proof(&bidders, &outsider), None);
+
+        let proof = build_proof(&bidders, &bidders[0]).unwrap();
+        assert!(!verify(&root, &outsider, &proof));
+        assert!(!verify(&root, &bidders[1], &proof));
+    }
+
+    #[test]
+    fn empty_list_has_no_root() {
+        assert_eq!(build_root(&[]), None);
+    }
+}
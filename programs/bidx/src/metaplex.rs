@@ -0,0 +1,178 @@
+//! Minimal Metaplex Token Metadata support, read straight from account data
+//! so the program doesn't pull in the mpl crates. Programmable NFTs sit in
+//! frozen token accounts and only move through Token Metadata's `TransferV1`,
+//! which is built by hand in `ProgrammableTransfer`.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction, program::invoke_signed, sysvar::instructions,
+};
+
+use crate::errors::AuctionError;
+
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// TokenStandard::ProgrammableNonFungible
+const PROGRAMMABLE_NON_FUNGIBLE: u8 = 4;
+
+// MetadataInstruction::Transfer, TransferArgs::V1
+const TRANSFER_V1: [u8; 2] = [49, 0];
+
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// The collection a metadata account belongs to, if it has been verified.
+pub fn verified_collection(metadata: &[u8]) -> Option<Pubkey> {
+    // edition_nonce and token_standard: Option<u8>
+    let mut offset = edition_nonce_offset(metadata)?;
+    for _ in 0..2 {
+        offset += if *metadata.get(offset)? == 1 { 2 } else { 1 };
+    }
+    // collection: Option<Collection { verified, key }>
+    if *metadata.get(offset)? != 1 || *metadata.get(offset + 1)? != 1 {
+        return None;
+    }
+    let key = metadata.get(offset + 2..offset + 34)?;
+    Pubkey::try_from(key).ok()
+}
+
+/// Whether the metadata marks its mint as a programmable NFT.
+pub fn is_programmable(metadata: &[u8]) -> bool {
+    let token_standard = || {
+        let mut offset = edition_nonce_offset(metadata)?;
+        offset += if *metadata.get(offset)? == 1 { 2 } else { 1 };
+        match *metadata.get(offset)? {
+            1 => metadata.get(offset + 1).copied(),
+            _ => None,
+        }
+    };
+    token_standard() == Some(PROGRAMMABLE_NON_FUNGIBLE)
+}
+
+// edition_nonce follows the variable-length part of the account
+fn edition_nonce_offset(metadata: &[u8]) -> Option<usize> {
+    // key, update_authority, mint
+    let mut offset = 1 + 32 + 32;
+    // name, symbol, uri
+    for _ in 0..3 {
+        offset += 4 + read_u32(metadata, offset)? as usize;
+    }
+    // seller_fee_basis_points
+    offset += 2;
+    // creators: Option<Vec<Creator { address, verified, share }>>
+    if *metadata.get(offset)? == 1 {
+        offset += 4 + read_u32(metadata, offset + 1)? as usize * 34;
+    }
+    offset += 1;
+    // primary_sale_happened, is_mutable
+    Some(offset + 2)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// Remaining accounts a pNFT transfer takes, see `ProgrammableTransfer::new`.
+pub const PROGRAMMABLE_ACCOUNTS: usize = 10;
+
+/// Token accounts on either side of a pNFT transfer. `owner` owns `from`
+/// and signs as the transfer authority.
+pub struct ItemTransfer<'info> {
+    pub from: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub to: AccountInfo<'info>,
+    pub to_owner: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// The Token Metadata accounts around a pNFT transfer.
+pub struct ProgrammableTransfer<'a, 'info> {
+    accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> ProgrammableTransfer<'a, 'info> {
+    /// `accounts` starts with, in order: the Token Metadata program, the
+    /// mint's metadata and master edition, the source and destination token
+    /// records, the auth rules program and rule set (the Token Metadata
+    /// program stands in for both without a rule set), the instructions
+    /// sysvar, the token program and the associated token program. Token
+    /// Metadata checks the PDAs and the rule set itself.
+    pub fn new(accounts: &'a [AccountInfo<'info>], mint: &Pubkey) -> Result<Self> {
+        require!(
+            accounts.len() >= PROGRAMMABLE_ACCOUNTS
+                && accounts[0].key() == TOKEN_METADATA_PROGRAM_ID
+                && accounts[1].key() == metadata_pda(mint)
+                && accounts[7].key() == instructions::ID,
+            AuctionError::ProgrammableAccountsRequired
+        );
+        Ok(Self { accounts: &accounts[..PROGRAMMABLE_ACCOUNTS] })
+    }
+
+    pub fn transfer(&self, item: ItemTransfer<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let [program, metadata, edition, owner_token_record, destination_token_record, rules_program, rules, sysvar_instructions, token_program, associated_token_program] =
+            self.accounts
+        else {
+            return err!(AuctionError::ProgrammableAccountsRequired);
+        };
+
+        let mut data = TRANSFER_V1.to_vec();
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.push(0); // authorization_data: None
+
+        let instruction = Instruction {
+            program_id: TOKEN_METADATA_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(item.from.key(), false),
+                AccountMeta::new_readonly(item.owner.key(), false),
+                AccountMeta::new(item.to.key(), false),
+                AccountMeta::new_readonly(item.to_owner.key(), false),
+                AccountMeta::new_readonly(item.mint.key(), false),
+                AccountMeta::new(metadata.key(), false),
+                AccountMeta::new_readonly(edition.key(), false),
+                AccountMeta::new(owner_token_record.key(), false),
+                AccountMeta::new(destination_token_record.key(), false),
+                AccountMeta::new_readonly(item.owner.key(), true),
+                AccountMeta::new(item.payer.key(), true),
+                AccountMeta::new_readonly(item.system_program.key(), false),
+                AccountMeta::new_readonly(sysvar_instructions.key(), false),
+                AccountMeta::new_readonly(token_program.key(), false),
+                AccountMeta::new_readonly(associated_token_program.key(), false),
+                AccountMeta::new_readonly(rules_program.key(), false),
+                AccountMeta::new_readonly(rules.key(), false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                item.from,
+                item.owner,
+                item.to,
+                item.to_owner,
+                item.mint,
+                metadata.clone(),
+                edition.clone(),
+                owner_token_record.clone(),
+                destination_token_record.clone(),
+                item.payer,
+                item.system_program,
+                sysvar_instructions.clone(),
+                token_program.clone(),
+                associated_token_program.clone(),
+                rules_program.clone(),
+                rules.clone(),
+                program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+}
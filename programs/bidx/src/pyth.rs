@@ -0,0 +1,84 @@
+//! Minimal Pyth pull-oracle support: `PriceUpdateV2` accounts posted by the
+//! Pyth receiver program, read straight from account data so the program
+//! doesn't pull in the Pyth SDK.
+
+use anchor_lang::prelude::*;
+
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// USD amounts priced through an oracle carry this many decimals.
+pub const USD_DECIMALS: u8 = 6;
+
+// sha256("account:PriceUpdateV2")[..8]
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// Borsh tag of VerificationLevel::Full, partially verified updates are refused
+const VERIFICATION_FULL: u8 = 1;
+
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// The price message of a fully verified `PriceUpdateV2` account.
+pub fn price_message(data: &[u8]) -> Option<PriceFeedMessage> {
+    if read::<8>(data, 0)? != PRICE_UPDATE_V2_DISCRIMINATOR {
+        return None;
+    }
+    // write_authority
+    let mut offset = 8 + 32;
+    // verification_level: Partial { num_signatures } | Full
+    if *data.get(offset)? != VERIFICATION_FULL {
+        return None;
+    }
+    offset += 1;
+
+    Some(PriceFeedMessage {
+        feed_id: read::<32>(data, offset)?,
+        price: i64::from_le_bytes(read(data, offset + 32)?),
+        conf: u64::from_le_bytes(read(data, offset + 40)?),
+        exponent: i32::from_le_bytes(read(data, offset + 48)?),
+        publish_time: i64::from_le_bytes(read(data, offset + 52)?),
+    })
+}
+
+impl PriceFeedMessage {
+    /// Value in USD (`USD_DECIMALS`) of `amount` raw units of a token with
+    /// `decimals` decimals, rounded down.
+    pub fn usd_value(&self, amount: u64, decimals: u8) -> Option<u64> {
+        let value = (amount as u128).checked_mul(u128::try_from(self.price).ok()?)?;
+        let scale = self.exponent + USD_DECIMALS as i32 - decimals as i32;
+        let value = if scale >= 0 {
+            value.checked_mul(10u128.checked_pow(scale as u32)?)?
+        } else {
+            value / 10u128.checked_pow(scale.unsigned_abs())?
+        };
+        u64::try_from(value).ok()
+    }
+
+    /// Raw units of a token with `decimals` decimals worth `usd`
+    /// (`USD_DECIMALS`), rounded up.
+    pub fn token_amount(&self, usd: u64, decimals: u8) -> Option<u64> {
+        let price = u128::try_from(self.price).ok().filter(|price| *price > 0)?;
+        let scale = self.exponent + USD_DECIMALS as i32 - decimals as i32;
+        let (value, price) = if scale >= 0 {
+            (usd as u128, price.checked_mul(10u128.checked_pow(scale as u32)?)?)
+        } else {
+            ((usd as u128).checked_mul(10u128.checked_pow(scale.unsigned_abs())?)?, price)
+        };
+        u64::try_from(value.div_ceil(price)).ok()
+    }
+
+    /// Confidence interval as basis points of the price.
+    pub fn conf_bps(&self) -> Option<u64> {
+        let price = u128::try_from(self.price).ok().filter(|price| *price > 0)?;
+        u64::try_from((self.conf as u128) * 10_000 / price).ok()
+    }
+}
+
+fn read<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset + N)?.try_into().ok()
+}
@@ -1,6 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::hash::hashv;
 
-use crate::states::AuthStatus;
+use crate::errors::{AuctionError, BidError};
+use crate::{merkle, metaplex, pyth};
+use crate::states::{AuthStatus, ParticipationBond, PlatformConfig};
+
+/// `accepted_token` of auctions paid in native lamports rather than an SPL token.
+pub const NATIVE_SOL: Pubkey = anchor_lang::system_program::ID;
+
+/// Fixed-point scale of `PaymentOption::rate`.
+pub const RATE_SCALE: u64 = 1_000_000_000;
+
+/// Most extra mints a seller can accept alongside `accepted_token`.
+pub const MAX_PAYMENT_OPTIONS: usize = 4;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum AssetType {
@@ -8,32 +21,598 @@ pub enum AssetType {
     PhysicalRWA,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ItemCustody {
+    Token,        // item_vault, moved with transfer_checked
+    Programmable, // Metaplex pNFT in item_vault, moved with Token Metadata's TransferV1
+    Compressed {  // Bubblegum leaf owned by the auction, no item_vault
+        tree: Pubkey,
+        leaf_index: u32,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CompressedLeaf {
+    pub root: [u8; 32], // root the proof in remaining accounts was taken against
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub leaf_index: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct LeafProof {
+    pub root: [u8; 32],
+    pub proof_len: u8, // proof nodes in remaining accounts, past the canopy
+}
+
 #[derive(Debug, Clone, InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq)]
 pub enum AuctionStatus {
     Pending,
     Paused,
     Active,
+    Revealing,
     Ended,
     Settled,
     Cancelled,
     Failed,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum PriceDecay {
+    Linear, // falls evenly from start_price at start_date to floor_price at end_date
+    Stepped { interval: i64, step: u64 }, // drops `step` every `interval` seconds
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum SealedBidPricing {
+    FirstPrice,  // winner pays their own bid
+    SecondPrice, // Vickrey: winner pays the runner-up bid (or the reserve if higher)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum AuctionType {
+    English,
+    Dutch {
+        start_price: u64,
+        floor_price: u64,
+        decay: PriceDecay,
+    },
+    SealedBid {
+        reveal_end_date: i64, // bids can be opened between end_date and this
+        penalty_bps: u16, // forfeited to the seller by unrevealed commitments
+        pricing: SealedBidPricing,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct IncrementTier {
+    pub from_price: u64, // tier applies once the highest bid reaches this
+    pub increment: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum BidIncrement {
+    Absolute { amount: u64 },
+    Bps { bps: u16 }, // of the current highest bid
+    Tiered {
+        #[max_len(8)]
+        tiers: Vec<IncrementTier>, // ascending by from_price
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum UnitPricing {
+    Uniform,  // every winner pays the lowest winning unit price
+    PayAsBid, // every winner pays their own unit price
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct MultiUnit {
+    pub quantity: u64, // units of nft_mint held in item_vault for sale
+    pub pricing: UnitPricing,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct SoftClose {
+    pub window: i64, // seconds before end_date in which a bid extends the auction
+    pub extension: i64, // seconds the end_date is pushed past the late bid
+    pub max_extensions: Option<u16>, // None = no cap
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct HiddenReserve {
+    pub commitment: [u8; 32], // sha256(reserve_le || salt || auction)
+    pub reveal_deadline: i64, // bidders are refunded if the reserve isn't revealed by then
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum BidderGate {
+    Mint { mint: Pubkey }, // any token of this mint
+    Collection { collection: Pubkey }, // any NFT verified in this Metaplex collection
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct UsdPricing {
+    pub feed_id: [u8; 32], // Pyth feed pricing accepted_token in USD
+    pub max_staleness: i64, // oldest price (seconds) the opening bid and settlement accept
+    pub max_conf_bps: u16, // widest confidence interval they accept, in bps of the price
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct PaymentOption {
+    pub mint: Pubkey,
+    pub rate: u64, // base units (accepted_token) per RATE_SCALE raw units of this mint
+}
+
+/// Optional terms a seller sets on creation, see the matching `Auction` fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AuctionTerms {
+    pub soft_close: Option<SoftClose>,
+    pub buy_now_price: Option<u64>,
+    pub min_increment: Option<BidIncrement>,
+    pub multi_unit: Option<MultiUnit>,
+    pub hidden_reserve: Option<HiddenReserve>,
+    pub allowlist_root: Option<[u8; 32]>,
+    pub bidder_gate: Option<BidderGate>,
+    pub participation_bond: Option<u64>,
+    pub payment_options: Vec<PaymentOption>,
+    pub usd_pricing: Option<UsdPricing>,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Auction {
     pub seller: Pubkey,
+    pub platform_config: Pubkey, // config the auction was created under, the only one later instructions accept
     pub nft_mint: Pubkey,
     pub item_vault: Pubkey,
+    pub item_custody: ItemCustody,
     pub asset_type: AssetType,
+    pub auction_type: AuctionType,
     pub starting_bid: u64,
     pub reserved_price: u64,
+    pub usd_pricing: Option<UsdPricing>, // Some = starting_bid and reserved_price are USD (pyth::USD_DECIMALS)
+    pub hidden_reserve: Option<HiddenReserve>, // Some until the committed reserve is revealed
+    pub allowlist_root: Option<[u8; 32]>, // Merkle root of invited bidders, see crate::merkle
+    pub bidder_gate: Option<BidderGate>, // None = anyone can bid
+    pub participation_bond: Option<u64>, // flat bond each bidder locks first (PhysicalRWA only)
+    pub buy_now_price: Option<u64>,
+    pub min_increment: Option<BidIncrement>, // None = any amount above the highest bid
+    pub multi_unit: Option<MultiUnit>, // None = single 1-of-1 item
+    pub units_settled: u64,
+    pub lot_items: u8, // extra items listed in the lot manifest, 0 = single item
     pub highest_bid: u64,
     pub highest_bidder: Pubkey,
-    pub accepted_token: Pubkey,
+    pub leader_max: u64, // escrowed maximum of the highest bidder (proxy bidding)
+    pub second_highest_bid: u64,
+    pub sealed_commitments: u32, // sealed bids committed, none of them priced until revealed
+    pub accepted_token: Pubkey, // base quote: every price and bid comparison is in its units
+    #[max_len(MAX_PAYMENT_OPTIONS)]
+    pub payment_options: Vec<PaymentOption>, // other mints bids may be escrowed in
     pub start_date: i64,
     pub end_date: i64,
+    pub soft_close: Option<SoftClose>,
+    pub extension_count: u16,
+    pub paused_at: i64, // 0 unless paused
     pub auction_status: AuctionStatus,
     pub auth_status: AuthStatus,
     pub bump: u8
 }
+
+impl Auction {
+    /// Commitment a seller submits for a hidden reserve: sha256(reserve_le || salt || auction).
+    pub fn reserve_commitment(reserve: u64, salt: &[u8; 32], auction: &Pubkey) -> [u8; 32] {
+        hashv(&[&reserve.to_le_bytes()[..], &salt[..], auction.as_ref()]).to_bytes()
+    }
+
+    /// Checks the seller-chosen terms against each other and the platform's
+    /// limits. Run on creation and again on every amendment.
+    pub fn validate_terms(&self, platform_config: &PlatformConfig) -> Result<()> {
+        require!(self.end_date > self.start_date, AuctionError::EndDateIsBehindStartDate);
+        require!(
+            self.end_date - self.start_date >= platform_config.min_auction_duration
+                && self.end_date - self.start_date <= platform_config.max_auction_duration,
+            AuctionError::InvalidDuration
+        );
+        match self.hidden_reserve.as_ref() {
+            // the reserve stays off-chain until revealed, so nothing may be priced against it
+            Some(hidden_reserve) => {
+                let bidding_closes = self.bidding_closes();
+                require!(
+                    self.reserved_price == 0
+                        && self.buy_now_price.is_none()
+                        && !matches!(self.auction_type, AuctionType::Dutch { .. })
+                        && hidden_reserve.reveal_deadline > bidding_closes,
+                    AuctionError::InvalidHiddenReserve
+                );
+            }
+            None => require!(self.reserved_price > self.starting_bid, AuctionError::ReservedPriceTooLow),
+        }
+        if let Some(soft_close) = self.soft_close.as_ref() {
+            require!(
+                soft_close.window > 0 && soft_close.extension > 0,
+                AuctionError::InvalidSoftClose
+            );
+        }
+        if let AuctionType::Dutch { start_price, floor_price, decay } = &self.auction_type {
+            require!(
+                start_price > floor_price && *floor_price >= self.reserved_price,
+                AuctionError::InvalidDutchPricing
+            );
+            // the first taker ends a Dutch auction, there is nothing to extend
+            require!(self.soft_close.is_none(), AuctionError::InvalidSoftClose);
+            if let PriceDecay::Stepped { interval, step } = decay {
+                require!(*interval > 0 && *step > 0, AuctionError::InvalidPriceDecay);
+            }
+        }
+        if let AuctionType::SealedBid { reveal_end_date, penalty_bps, .. } = &self.auction_type {
+            require!(
+                *reveal_end_date > self.end_date && *penalty_bps <= 10_000,
+                AuctionError::InvalidSealedBidConfig
+            );
+            // late commitments can't be extended past a fixed reveal phase
+            require!(self.soft_close.is_none(), AuctionError::InvalidSoftClose);
+        }
+        if let Some(buy_now_price) = self.buy_now_price {
+            require!(
+                self.auction_type == AuctionType::English && buy_now_price >= self.reserved_price,
+                AuctionError::InvalidBuyNowPrice
+            );
+        }
+        if let Some(min_increment) = self.min_increment.as_ref() {
+            let valid = match min_increment {
+                BidIncrement::Absolute { amount } => *amount > 0,
+                BidIncrement::Bps { bps } => *bps > 0 && *bps <= 10_000,
+                BidIncrement::Tiered { tiers } => {
+                    !tiers.is_empty()
+                        && tiers.iter().all(|tier| tier.increment > 0)
+                        && tiers.windows(2).all(|pair| pair[0].from_price < pair[1].from_price)
+                }
+            };
+            require!(valid, AuctionError::InvalidBidIncrement);
+        }
+        if let Some(participation_bond) = self.participation_bond {
+            require!(
+                participation_bond > 0 && self.asset_type == AssetType::PhysicalRWA,
+                AuctionError::InvalidParticipationBond
+            );
+        }
+        // lamports are only escrowed by place_bid, so native SOL is open or Dutch bidding only
+        if self.accepted_token == NATIVE_SOL {
+            require!(
+                !matches!(self.auction_type, AuctionType::SealedBid { .. })
+                    && self.buy_now_price.is_none()
+                    && self.multi_unit.is_none()
+                    && self.participation_bond.is_none(),
+                AuctionError::NativeSolUnsupported
+            );
+        }
+        // only open English bids are converted, every other path escrows accepted_token;
+        // bonds are released in the bid's mint, so they stay with accepted_token too
+        if !self.payment_options.is_empty() {
+            require!(
+                self.auction_type == AuctionType::English
+                    && self.multi_unit.is_none()
+                    && self.participation_bond.is_none()
+                    && self.accepted_token != NATIVE_SOL
+                    && self.payment_options.len() <= MAX_PAYMENT_OPTIONS,
+                AuctionError::InvalidPaymentOptions
+            );
+            for (i, option) in self.payment_options.iter().enumerate() {
+                require!(
+                    option.rate > 0
+                        && option.mint != self.accepted_token
+                        && option.mint != NATIVE_SOL
+                        && self.payment_options[..i].iter().all(|other| other.mint != option.mint),
+                    AuctionError::InvalidPaymentOptions
+                );
+            }
+        }
+        // USD terms are only priced for the opening bid and at settlement, so nothing else may be compared against them
+        if let Some(usd_pricing) = self.usd_pricing.as_ref() {
+            require!(
+                self.auction_type == AuctionType::English
+                    && self.hidden_reserve.is_none()
+                    && self.buy_now_price.is_none()
+                    && self.multi_unit.is_none()
+                    && self.payment_options.is_empty()
+                    && usd_pricing.max_staleness > 0
+                    && usd_pricing.max_conf_bps > 0
+                    && usd_pricing.max_conf_bps <= 10_000,
+                AuctionError::InvalidUsdPricing
+            );
+        }
+        if let Some(multi_unit) = self.multi_unit.as_ref() {
+            require!(
+                self.auction_type == AuctionType::English
+                    && self.buy_now_price.is_none()
+                    && multi_unit.quantity > 0,
+                AuctionError::InvalidMultiUnit
+            );
+        }
+
+        Ok(())
+    }
+
+    /// When the last bid can still come in: the end of the reveal phase for
+    /// sealed bids, `end_date` otherwise.
+    pub fn bidding_closes(&self) -> i64 {
+        match &self.auction_type {
+            AuctionType::SealedBid { reveal_end_date, .. } => *reveal_end_date,
+            _ => self.end_date,
+        }
+    }
+
+    /// Conversion rate of `mint` into base units, `None` if the auction doesn't accept it.
+    pub fn payment_rate(&self, mint: &Pubkey) -> Option<u64> {
+        if *mint == self.accepted_token {
+            return Some(RATE_SCALE);
+        }
+        self.payment_options
+            .iter()
+            .find(|option| option.mint == *mint)
+            .map(|option| option.rate)
+    }
+
+    /// Base-unit value of `amount` escrowed in `mint`, rounded down.
+    pub fn to_base(&self, mint: &Pubkey, amount: u64) -> Result<u64> {
+        let rate = self.payment_rate(mint).ok_or(BidError::WrongToken)?;
+        let base = (amount as u128) * (rate as u128) / (RATE_SCALE as u128);
+        u64::try_from(base).map_err(|_| error!(BidError::ConversionOverflow))
+    }
+
+    /// Amount of `mint` worth `base` base units, rounded up so the seller
+    /// never receives less than the price.
+    pub fn from_base(&self, mint: &Pubkey, base: u64) -> Result<u64> {
+        let rate = self.payment_rate(mint).ok_or(BidError::WrongToken)? as u128;
+        let amount = ((base as u128) * (RATE_SCALE as u128)).div_ceil(rate);
+        u64::try_from(amount).map_err(|_| error!(BidError::ConversionOverflow))
+    }
+
+    /// A proxy maximum that covers the reserve bids at least the reserve.
+    /// USD reserves are lifted to at settlement, see `usd_clearing_price`.
+    pub fn lift_to_reserve(&mut self) {
+        if self.auction_type == AuctionType::English
+            && self.hidden_reserve.is_none()
+            && self.usd_pricing.is_none()
+            && self.leader_max >= self.reserved_price
+            && self.highest_bid < self.reserved_price
+        {
+            self.highest_bid = self.reserved_price;
+        }
+    }
+
+    /// A single-item auction whose highest bid fell short of a known reserve
+    /// can't settle. USD reserves are priced by the oracle, see
+    /// `usd_clearing_price`.
+    pub fn reserve_unmet(&self) -> bool {
+        self.multi_unit.is_none()
+            && self.hidden_reserve.is_none()
+            && self.usd_pricing.is_none()
+            && self.highest_bid < self.reserved_price
+    }
+
+    /// An ended auction nobody can settle: its highest bid fell short of a
+    /// known reserve, or its hidden reserve went unrevealed past the deadline.
+    pub fn has_failed(&self, now: i64) -> bool {
+        self.auction_status == AuctionStatus::Ended
+            && (self.reserve_unmet()
+                || self
+                    .hidden_reserve
+                    .as_ref()
+                    .is_some_and(|hidden_reserve| now > hidden_reserve.reveal_deadline))
+    }
+
+    /// Invitation-only auctions need a Merkle proof for the bidder.
+    pub fn require_allowlisted(&self, bidder: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+        if let Some(root) = self.allowlist_root.as_ref() {
+            require!(merkle::verify(root, bidder, proof), BidError::NotAllowlisted);
+        }
+        Ok(())
+    }
+
+    /// Holder-only auctions need a token account of the bidder's holding the
+    /// gating token, plus its metadata account for collection gates.
+    pub fn require_gate_holder<'info>(
+        &self,
+        bidder: &Pubkey,
+        holder_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        holder_metadata: Option<&AccountInfo<'info>>,
+    ) -> Result<()> {
+        if let Some(gate) = self.bidder_gate.as_ref() {
+            let holder_account = holder_account.ok_or(BidError::GateAccountsRequired)?;
+            require!(
+                holder_account.owner == *bidder && holder_account.amount > 0,
+                BidError::NotTokenHolder
+            );
+            match gate {
+                BidderGate::Mint { mint } => {
+                    require_keys_eq!(holder_account.mint, *mint, BidError::NotTokenHolder);
+                }
+                BidderGate::Collection { collection } => {
+                    let metadata = holder_metadata.ok_or(BidError::GateAccountsRequired)?;
+                    require!(
+                        *metadata.owner == metaplex::TOKEN_METADATA_PROGRAM_ID
+                            && metadata.key() == metaplex::metadata_pda(&holder_account.mint),
+                        BidError::InvalidGateMetadata
+                    );
+                    require!(
+                        metaplex::verified_collection(&metadata.try_borrow_data()?) == Some(*collection),
+                        BidError::NotTokenHolder
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Bonded auctions only take bids from wallets that posted the bond.
+    pub fn require_bond_posted(&self, bond: Option<&Account<ParticipationBond>>) -> Result<()> {
+        if let Some(participation_bond) = self.participation_bond {
+            require!(
+                bond.is_some_and(|bond| bond.amount >= participation_bond),
+                BidError::BondRequired
+            );
+        }
+        Ok(())
+    }
+
+    /// Bids are only accepted from start_date until end_date.
+    pub fn require_bidding_open(&self, now: i64) -> Result<()> {
+        require!(now >= self.start_date, BidError::NotStarted);
+        require!(now < self.end_date, BidError::BiddingClosed);
+        Ok(())
+    }
+
+    /// Smallest amount the next open bid must reach: the starting bid for the
+    /// first bid, otherwise the highest bid plus the configured increment.
+    /// `price_update` and `decimals` price a USD starting bid, see `opening_bid`.
+    pub fn min_next_bid(
+        &self,
+        price_update: Option<&AccountInfo>,
+        decimals: u8,
+        now: i64,
+    ) -> Result<u64> {
+        if self.highest_bidder == Pubkey::default() {
+            return self.opening_bid(price_update, decimals, now);
+        }
+
+        Ok(self.raise_over(self.highest_bid, u64::MAX))
+    }
+
+    /// Lowest first bid in the bid token (with `decimals` decimals). USD
+    /// starting bids are converted at the price in `price_update`, rounded up.
+    pub fn opening_bid(
+        &self,
+        price_update: Option<&AccountInfo>,
+        decimals: u8,
+        now: i64,
+    ) -> Result<u64> {
+        if self.usd_pricing.is_none() {
+            return Ok(self.starting_bid);
+        }
+        self.usd_price(price_update, now)?
+            .token_amount(self.starting_bid, decimals)
+            .ok_or(error!(AuctionError::InvalidPriceUpdate))
+    }
+
+    /// What the winner of a USD-priced auction pays in the bid token (with
+    /// `decimals` decimals): the highest bid, lifted to the USD reserve when
+    /// the leader's maximum covers it. `None` when even the maximum is worth
+    /// less than the reserve, which fails the auction.
+    pub fn usd_clearing_price(
+        &self,
+        price_update: Option<&AccountInfo>,
+        decimals: u8,
+        now: i64,
+    ) -> Result<Option<u64>> {
+        let price = self.usd_price(price_update, now)?;
+        let usd_value = |amount| {
+            price
+                .usd_value(amount, decimals)
+                .ok_or(error!(AuctionError::InvalidPriceUpdate))
+        };
+
+        if usd_value(self.highest_bid)? >= self.reserved_price {
+            return Ok(Some(self.highest_bid));
+        }
+        if usd_value(self.leader_max)? < self.reserved_price {
+            return Ok(None);
+        }
+        let reserve = price
+            .token_amount(self.reserved_price, decimals)
+            .ok_or(AuctionError::InvalidPriceUpdate)?;
+        Ok(Some(reserve.min(self.leader_max)))
+    }
+
+    /// Price message of `price_update`, which must be a fully verified Pyth
+    /// update for the auction's feed that is fresh and tight enough.
+    fn usd_price(&self, price_update: Option<&AccountInfo>, now: i64) -> Result<pyth::PriceFeedMessage> {
+        let usd_pricing = self.usd_pricing.as_ref().ok_or(AuctionError::InvalidUsdPricing)?;
+        let price_update = price_update.ok_or(AuctionError::PriceUpdateRequired)?;
+        require_keys_eq!(
+            *price_update.owner,
+            pyth::PYTH_RECEIVER_PROGRAM_ID,
+            AuctionError::InvalidPriceUpdate
+        );
+        let message = pyth::price_message(&price_update.try_borrow_data()?)
+            .filter(|message| message.feed_id == usd_pricing.feed_id && message.price > 0)
+            .ok_or(AuctionError::InvalidPriceUpdate)?;
+
+        require!(
+            now - message.publish_time <= usd_pricing.max_staleness,
+            AuctionError::StalePrice
+        );
+        require!(
+            message
+                .conf_bps()
+                .is_some_and(|conf_bps| conf_bps <= usd_pricing.max_conf_bps as u64),
+            AuctionError::PriceConfidenceTooWide
+        );
+
+        Ok(message)
+    }
+
+    /// One configured increment above `price`, capped at `cap`. Proxy bids
+    /// resolve to the second-highest maximum raised this way.
+    pub fn raise_over(&self, price: u64, cap: u64) -> u64 {
+        let increment = match &self.min_increment {
+            None => 1,
+            Some(BidIncrement::Absolute { amount }) => *amount,
+            Some(BidIncrement::Bps { bps }) => {
+                ((price as u128) * (*bps as u128) / 10_000).max(1) as u64
+            }
+            Some(BidIncrement::Tiered { tiers }) => tiers
+                .iter()
+                .take_while(|tier| tier.from_price <= price)
+                .last()
+                .or(tiers.first())
+                .map_or(1, |tier| tier.increment),
+        };
+
+        price.saturating_add(increment).min(cap)
+    }
+
+    /// Price the winner pays: the second-highest revealed bid (floored at the
+    /// reserve) for Vickrey auctions, otherwise the highest bid.
+    pub fn clearing_price(&self) -> u64 {
+        match self.auction_type {
+            AuctionType::SealedBid { pricing: SealedBidPricing::SecondPrice, .. } => {
+                self.second_highest_bid.max(self.reserved_price)
+            }
+            _ => self.highest_bid,
+        }
+    }
+
+    /// Current asking price of a Dutch auction, `None` for every other type.
+    pub fn dutch_price(&self, now: i64) -> Option<u64> {
+        let AuctionType::Dutch { start_price, floor_price, decay } = &self.auction_type else {
+            return None;
+        };
+
+        if now <= self.start_date {
+            return Some(*start_price);
+        }
+
+        let elapsed = now - self.start_date;
+        let drop = match decay {
+            PriceDecay::Linear => {
+                let duration = self.end_date - self.start_date;
+                if elapsed >= duration {
+                    return Some(*floor_price);
+                }
+                ((start_price - floor_price) as u128)
+                    .checked_mul(elapsed as u128)?
+                    .checked_div(duration as u128)? as u64
+            }
+            PriceDecay::Stepped { interval, step } => {
+                let steps = (elapsed / interval) as u64;
+                steps.saturating_mul(*step)
+            }
+        };
+
+        Some(start_price.saturating_sub(drop).max(*floor_price))
+    }
+}
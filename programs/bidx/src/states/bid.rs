@@ -1,14 +1,187 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount};
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::hash::hashv;
+
+use crate::errors::BidError;
+use crate::extensions;
+use crate::states::NATIVE_SOL;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Bid {
     pub bidder: Pubkey,
     pub amount: u64,
+    pub quantity: u64, // units bid for (1 outside multi-unit auctions)
     pub auction: Pubkey,
     pub token_mint: Pubkey,
     pub time_stamp: i64,
     pub is_active: bool,
     pub is_winner: bool,
+    pub commitment: Option<[u8; 32]>, // sealed-bid hash, None for open bids
+    pub revealed: bool,
     pub bump: u8,
 }
+
+impl Bid {
+    /// Commitment a sealed bidder submits: sha256(amount_le || salt || bidder).
+    pub fn sealed_commitment(amount: u64, salt: &[u8; 32], bidder: &Pubkey) -> [u8; 32] {
+        hashv(&[&amount.to_le_bytes()[..], &salt[..], bidder.as_ref()]).to_bytes()
+    }
+}
+
+/// Where a bid's funds sit: a token vault owned by the Bid PDA, or lamports on
+/// the Bid PDA itself for native SOL auctions.
+pub enum Escrow<'info> {
+    Token {
+        vault: AccountInfo<'info>,
+        mint: AccountInfo<'info>,
+        decimals: u8,
+        token_program: AccountInfo<'info>,
+        hook_accounts: Vec<AccountInfo<'info>>, // see extensions::transfer_checked
+    },
+    Lamports,
+}
+
+impl<'info> Escrow<'info> {
+    /// `payment_mint` is the auction's (or bid's) token, `NATIVE_SOL` for lamports.
+    pub fn new(
+        payment_mint: &Pubkey,
+        vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+        mint: Option<&InterfaceAccount<'info, Mint>>,
+        token_program: &AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<Self> {
+        if *payment_mint == NATIVE_SOL {
+            return Ok(Escrow::Lamports);
+        }
+
+        let (Some(vault), Some(mint)) = (vault, mint) else {
+            return err!(BidError::TokenAccountsRequired);
+        };
+        require_keys_eq!(mint.key(), *payment_mint, BidError::WrongToken);
+
+        Ok(Escrow::Token {
+            vault: vault.to_account_info(),
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            token_program: token_program.clone(),
+            hook_accounts: hook_accounts.to_vec(),
+        })
+    }
+
+    /// Decimals of the escrowed token, 9 for lamports.
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Escrow::Token { decimals, .. } => *decimals,
+            Escrow::Lamports => 9,
+        }
+    }
+
+    /// What escrow receives when `amount` is deposited, net of transfer fees.
+    pub fn received(&self, amount: u64) -> Result<u64> {
+        match self {
+            Escrow::Token { mint, .. } => extensions::received(mint, amount),
+            Escrow::Lamports => Ok(amount),
+        }
+    }
+
+    /// What has to be sent for `net` to arrive after transfer fees.
+    pub fn gross_up(&self, net: u64) -> Result<u64> {
+        match self {
+            Escrow::Token { mint, .. } => extensions::gross_up(mint, net),
+            Escrow::Lamports => Ok(net),
+        }
+    }
+
+    /// Moves `amount` from the bidder into escrow.
+    pub fn deposit(
+        &self,
+        bidder: &AccountInfo<'info>,
+        bidder_token_account: Option<AccountInfo<'info>>,
+        bid: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        match self {
+            Escrow::Token { vault, mint, decimals, token_program, hook_accounts } => {
+                extensions::transfer_checked(
+                    token_program,
+                    bidder_token_account.ok_or(BidError::TokenAccountsRequired)?,
+                    mint.clone(),
+                    vault.clone(),
+                    bidder.clone(),
+                    hook_accounts,
+                    amount,
+                    *decimals,
+                    &[],
+                )
+            }
+            Escrow::Lamports => transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: bidder.clone(),
+                        to: bid.clone(),
+                    },
+                ),
+                amount,
+            ),
+        }
+    }
+
+    /// Pays `amount` out of escrow: to `token_account` for token auctions, or
+    /// straight onto `wallet` for native SOL, which the program can debit
+    /// from the Bid PDA it owns without a system transfer.
+    pub fn pay(
+        &self,
+        bid: &AccountInfo<'info>,
+        token_account: Option<AccountInfo<'info>>,
+        wallet: &AccountInfo<'info>,
+        amount: u64,
+        bid_signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        match self {
+            Escrow::Token { vault, mint, decimals, token_program, hook_accounts } => {
+                extensions::transfer_checked(
+                    token_program,
+                    vault.clone(),
+                    mint.clone(),
+                    token_account.ok_or(BidError::TokenAccountsRequired)?,
+                    bid.clone(),
+                    hook_accounts,
+                    amount,
+                    *decimals,
+                    bid_signer_seeds,
+                )
+            }
+            Escrow::Lamports => {
+                bid.sub_lamports(amount)?;
+                wallet.add_lamports(amount)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Closes the token vault once it is empty, harvesting any withheld
+    /// transfer fees to the mint first; lamport escrow goes with the Bid.
+    pub fn close(&self, bid: &AccountInfo<'info>, destination: &AccountInfo<'info>, bid_signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        match self {
+            Escrow::Token { vault, mint, token_program, .. } => {
+                extensions::harvest_withheld_fees(token_program, mint, vault)?;
+                close_account(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        CloseAccount {
+                            account: vault.clone(),
+                            destination: destination.clone(),
+                            authority: bid.clone(),
+                        },
+                        bid_signer_seeds,
+                    ),
+                )
+            }
+            Escrow::Lamports => Ok(()),
+        }
+    }
+}
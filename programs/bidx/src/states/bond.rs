@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::BidError;
+use crate::extensions;
+use crate::states::Auction;
+
+/// Seconds a winner has to settle once bidding closes; past it an unsettled
+/// winner has abandoned the lot and their bond can be forfeited.
+pub const SETTLEMENT_WINDOW: i64 = 7 * 24 * 60 * 60;
+
+/// Grounds on which the platform admin forfeits a bond, see forfeit_bond.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ForfeitReason {
+    ShillBidding, // the bidder's open bid inflated the price for the seller
+    AbandonedLot, // the winner left the lot unsettled past SETTLEMENT_WINDOW
+}
+
+// Flat bond a bidder locks in the auction's bond vault before bidding
+#[account]
+#[derive(InitSpace)]
+pub struct ParticipationBond {
+    pub bidder: Pubkey,
+    pub auction: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl ParticipationBond {
+    /// Moves the bond out of the auction-owned bond vault to `destination`,
+    /// the bidder's account on release or the treasury on forfeit.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_out<'info>(
+        &self,
+        auction: &Account<'info, Auction>,
+        bond_vault: &InterfaceAccount<'info, TokenAccount>,
+        destination: AccountInfo<'info>,
+        token_mint: &InterfaceAccount<'info, Mint>,
+        token_program: AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+        auction_signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        // the auction PDA also owns the item vault, so pin the vault to the bond token
+        require!(
+            token_mint.key() == auction.accepted_token && bond_vault.mint == auction.accepted_token,
+            BidError::WrongToken
+        );
+
+        extensions::transfer_checked(
+            &token_program,
+            bond_vault.to_account_info(),
+            token_mint.to_account_info(),
+            destination,
+            auction.to_account_info(),
+            hook_accounts,
+            self.amount,
+            token_mint.decimals,
+            auction_signer_seeds,
+        )
+    }
+}
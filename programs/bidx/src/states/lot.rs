@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::AuctionError;
+use crate::extensions;
+
+pub const MAX_LOT_ITEMS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct LotItem {
+    pub mint: Pubkey,
+    pub vault: Pubkey, // auction-owned token account holding the item
+    pub amount: u64,
+}
+
+// Items sold alongside the auction's primary nft_mint / item_vault
+#[account]
+#[derive(InitSpace)]
+pub struct LotManifest {
+    pub auction: Pubkey,
+    #[max_len(8)]
+    pub items: Vec<LotItem>,
+    pub bump: u8,
+}
+
+impl LotManifest {
+    /// Moves every lot item out of its auction-owned vault to `recipient`.
+    /// `lot_accounts` holds a (mint, vault, recipient token account) triple per item,
+    /// `hook_accounts` whatever transfer-hook item mints need.
+    pub fn transfer_items<'info>(
+        &self,
+        lot_accounts: &[AccountInfo<'info>],
+        hook_accounts: &[AccountInfo<'info>],
+        recipient: &Pubkey,
+        token_program: AccountInfo<'info>,
+        auction: AccountInfo<'info>,
+        auction_signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        require!(
+            lot_accounts.len() == self.items.len() * 3,
+            AuctionError::LotAccountsRequired
+        );
+
+        for (item, accounts) in self.items.iter().zip(lot_accounts.chunks(3)) {
+            let (mint_info, vault_info, destination_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            require!(
+                mint_info.key() == item.mint
+                    && vault_info.key() == item.vault
+                    && *destination_info.owner == token_program.key(),
+                AuctionError::LotAccountsRequired
+            );
+
+            let mint = Mint::try_deserialize(&mut &mint_info.try_borrow_data()?[..])?;
+            let destination = TokenAccount::try_deserialize(&mut &destination_info.try_borrow_data()?[..])?;
+            require!(
+                destination.mint == item.mint && destination.owner == *recipient,
+                AuctionError::LotAccountsRequired
+            );
+
+            extensions::transfer_checked(
+                &token_program,
+                vault_info.clone(),
+                mint_info.clone(),
+                destination_info.clone(),
+                auction.clone(),
+                hook_accounts,
+                item.amount,
+                mint.decimals,
+                auction_signer_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -2,12 +2,18 @@ pub mod auction;
 pub mod authentication;
 pub mod authenticators;
 pub mod bid;
+pub mod bond;
+pub mod lot;
 pub mod platform;
 pub mod seller;
+pub mod unit_book;
 
 pub use auction::*;
 pub use authentication::*;
 pub use authenticators::*;
 pub use bid::*;
+pub use bond::*;
+pub use lot::*;
 pub use platform::*;
-pub use seller::*;
\ No newline at end of file
+pub use seller::*;
+pub use unit_book::*;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
 #[account]
 #[derive(InitSpace)]
@@ -12,4 +13,21 @@ pub struct PlatformConfig {
     pub min_auction_duration: i64,
     pub max_auction_duration: i64,
     pub bump: u8,
+    pub cancellation_fee_bps: u16,  // charged on the highest bid to cancel once bids exist (0 = not allowed)
+}
+
+impl PlatformConfig {
+    /// Whether `treasury` collects fees paid in `mint`: the USDC or wSOL
+    /// treasury, or the vault opened for `mint` by open_treasury_vault.
+    /// `config` is this account's address.
+    pub fn is_fee_treasury(
+        &self,
+        config: &Pubkey,
+        treasury: &InterfaceAccount<TokenAccount>,
+        mint: &Pubkey,
+    ) -> bool {
+        treasury.key() == self.treasury_usdc
+            || treasury.key() == self.treasury_sol
+            || (treasury.owner == *config && treasury.mint == *mint)
+    }
 }
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BidError;
+use crate::states::{MultiUnit, UnitPricing};
+
+pub const MAX_UNIT_BIDS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct UnitBid {
+    pub bidder: Pubkey,
+    pub unit_price: u64,
+    pub quantity: u64,
+}
+
+// Standing bids of a multi-unit auction, best unit price first
+#[account]
+#[derive(InitSpace)]
+pub struct UnitBook {
+    pub auction: Pubkey,
+    #[max_len(16)]
+    pub entries: Vec<UnitBid>,
+    pub bump: u8,
+}
+
+impl UnitBook {
+    /// Inserts (or replaces) a bidder's entry behind every bid at the same or
+    /// better price, then drops entries that no longer win any unit.
+    pub fn insert(&mut self, entry: UnitBid, units_for_sale: u64) -> Result<()> {
+        self.entries.retain(|e| e.bidder != entry.bidder);
+
+        let position = self
+            .entries
+            .iter()
+            .position(|e| e.unit_price < entry.unit_price)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+
+        let mut allocated = 0u64;
+        self.entries.retain(|e| {
+            let wins = allocated < units_for_sale;
+            allocated = allocated.saturating_add(e.quantity);
+            wins
+        });
+        // every entry left still wins a unit, so none can be dropped to make room
+        require!(self.entries.len() <= MAX_UNIT_BIDS, BidError::UnitBookFull);
+        Ok(())
+    }
+
+    /// Units won by `bidder` and the unit price they bid.
+    pub fn allocation(&self, bidder: &Pubkey, units_for_sale: u64) -> Option<(u64, u64)> {
+        let mut remaining = units_for_sale;
+        for entry in &self.entries {
+            let units = entry.quantity.min(remaining);
+            if entry.bidder == *bidder {
+                return (units > 0).then_some((units, entry.unit_price));
+            }
+            remaining -= units;
+        }
+        None
+    }
+
+    pub fn allocated_units(&self, units_for_sale: u64) -> u64 {
+        self.entries
+            .iter()
+            .fold(0u64, |total, e| total.saturating_add(e.quantity))
+            .min(units_for_sale)
+    }
+
+    /// Units won at a unit price that meets `reserve`, the ones that can settle.
+    pub fn settleable_units(&self, multi_unit: &MultiUnit, reserve: u64) -> u64 {
+        let mut remaining = multi_unit.quantity;
+        let mut settleable = 0u64;
+        for entry in &self.entries {
+            let units = entry.quantity.min(remaining);
+            remaining -= units;
+            if self.unit_price(&multi_unit.pricing, entry.unit_price) >= reserve {
+                settleable += units;
+            }
+        }
+        settleable
+    }
+
+    /// Unit price paid by a winner who bid `bid_unit_price`.
+    pub fn unit_price(&self, pricing: &UnitPricing, bid_unit_price: u64) -> u64 {
+        match pricing {
+            UnitPricing::Uniform => self.clearing_price(),
+            UnitPricing::PayAsBid => bid_unit_price,
+        }
+    }
+
+    /// Lowest unit price that still wins a unit (the uniform clearing price).
+    pub fn clearing_price(&self) -> u64 {
+        self.entries.last().map_or(0, |e| e.unit_price)
+    }
+}
@@ -0,0 +1,100 @@
+//! Minimal Token-2022 transfer hook for the bidx test suite. Every transfer
+//! of a hooked mint bumps a per-mint counter, which is also the one extra
+//! account the hook asks for, so tests can tell the hook ran with the
+//! accounts bidx forwarded.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+declare_id!("9GxcaUhbqGYv8xoGpZ6odVz9Jktuimty2ENbU1iiDYW6");
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+pub const COUNTER_SEED: &[u8] = b"counter";
+
+#[program]
+pub mod mock_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(ctx: Context<InitializeExtraAccountMetaList>) -> Result<()> {
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_account_metas()?,
+        )?;
+        ctx.accounts.counter.bump = ctx.bumps.counter;
+        Ok(())
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        let _ = amount;
+        ctx.accounts.counter.transfers += 1;
+        Ok(())
+    }
+}
+
+// the counter PDA, derived from the mint (account index 1 of Execute)
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal { bytes: COUNTER_SEED.to_vec() },
+            Seed::AccountKey { index: 1 },
+        ],
+        false,
+        true,
+    )?])
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TransferCounter {
+    pub transfers: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: TLV list written by ExtraAccountMetaList::init
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(1)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TransferCounter::INIT_SPACE,
+        seeds = [COUNTER_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub counter: Account<'info, TransferCounter>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
+
+// account order fixed by the transfer-hook interface
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// CHECK: source token account
+    pub source: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: destination token account
+    pub destination: UncheckedAccount<'info>,
+    /// CHECK: source owner or delegate
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: validated by its seeds
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut, seeds = [COUNTER_SEED, mint.key().as_ref()], bump = counter.bump)]
+    pub counter: Account<'info, TransferCounter>,
+}
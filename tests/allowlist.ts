@@ -0,0 +1,90 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  merkleProof,
+  merkleRoot,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runAllowlistTests(getCtx: () => Ctx) {
+  describe("allowlisted auctions", () => {
+    const invited = [...Array(5)].map(() => Keypair.generate());
+    const invitedKeys = invited.map((kp) => kp.publicKey);
+    let auctionCtx: AuctionContext;
+
+    before(async () => {
+      const { program, connection, platform } = getCtx();
+      auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { allowlistRoot: merkleRoot(invitedKeys) },
+      );
+    });
+
+    it("accepts a bid with a valid proof", async () => {
+      const { program, connection, platform } = getCtx();
+      const bidder = invited[3];
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+        false,
+        bidder,
+        merkleProof(invitedKeys, bidder.publicKey),
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        bidder.publicKey.toBase58(),
+      );
+    });
+
+    it("rejects uninvited bidders and borrowed proofs", async () => {
+      const { program, connection, platform } = getCtx();
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(3_000_000),
+        ),
+        "NotAllowlisted",
+      );
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(3_000_000),
+          false,
+          Keypair.generate(),
+          merkleProof(invitedKeys, invited[0].publicKey),
+        ),
+        "NotAllowlisted",
+      );
+    });
+  });
+}
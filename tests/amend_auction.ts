@@ -0,0 +1,118 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runAmendAuctionTests(getCtx: () => Ctx) {
+  describe("amend_auction", () => {
+    function amend(
+      auctionCtx: AuctionContext,
+      startingBid: BN | null,
+      reservedPrice: BN | null,
+      endDate: BN | null,
+      buyNowPrice: BN | null,
+    ) {
+      const { program, platform } = getCtx();
+      return program.methods
+        .amendAuction(
+          new BN(0),
+          startingBid,
+          reservedPrice,
+          endDate,
+          buyNowPrice,
+        )
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          platformConfig: platform.platformConfig,
+        })
+        .signers([auctionCtx.seller])
+        .rpc();
+    }
+
+    it("amends terms freely before the first bid", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      const before = await program.account.auction.fetch(auctionCtx.auction);
+
+      await amend(
+        auctionCtx,
+        new BN(2_000_000),
+        new BN(8_000_000),
+        before.endDate.addn(5),
+        new BN(20_000_000),
+      );
+
+      const after = await program.account.auction.fetch(auctionCtx.auction);
+      expect(after.startingBid.toNumber()).to.equal(2_000_000);
+      expect(after.reservedPrice.toNumber()).to.equal(8_000_000);
+      expect(after.endDate.toNumber()).to.equal(before.endDate.toNumber() + 5);
+      expect(after.buyNowPrice.toNumber()).to.equal(20_000_000);
+    });
+
+    it("re-runs the creation validations", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+
+      await assertAnchorError(
+        amend(auctionCtx, new BN(6_000_000), null, null, null),
+        "ReservedPriceTooLow",
+      );
+    });
+
+    it("only allows changes that favour bidders once bids exist", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+
+      await assertAnchorError(
+        amend(auctionCtx, null, new BN(6_000_000), null, null),
+        "AmendmentDisfavoursBidders",
+      );
+      const current = await program.account.auction.fetch(auctionCtx.auction);
+      await assertAnchorError(
+        amend(auctionCtx, null, null, current.endDate.addn(5), null),
+        "AmendmentDisfavoursBidders",
+      );
+
+      // re-stating the advertised close isn't an amendment
+      await amend(auctionCtx, null, null, current.endDate, null);
+
+      await amend(auctionCtx, null, new BN(4_000_000), null, null);
+
+      const after = await program.account.auction.fetch(auctionCtx.auction);
+      expect(after.reservedPrice.toNumber()).to.equal(4_000_000);
+    });
+  });
+}
@@ -21,6 +21,23 @@ import { runInitializeTests } from "./initialize";
 import { runCreateAuctionTests } from "./create_auction";
 import { runPlaceBidTests } from "./place_bid";
 import { runSettleAndWithdrawTests } from "./settle_and_withdraw";
+import { runSealedBidTests } from "./sealed_bid";
+import { runBuyNowTests } from "./buy_now";
+import { runMultiUnitTests } from "./multi_unit";
+import { runLotTests } from "./lot";
+import { runHiddenReserveTests } from "./hidden_reserve";
+import { runCancelAuctionTests } from "./cancel_auction";
+import { runPauseAuctionTests } from "./pause_auction";
+import { runAmendAuctionTests } from "./amend_auction";
+import { runAllowlistTests } from "./allowlist";
+import { runTokenGateTests } from "./token_gate";
+import { runBondTests } from "./bond";
+import { runNativeSolTests } from "./native_sol";
+import { runPaymentOptionsTests } from "./payment_options";
+import { runOracleTests } from "./oracle";
+import { runToken2022Tests } from "./token_2022";
+import { runPnftTests } from "./pnft";
+import { runCnftTests } from "./cnft";
 
 describe("bidx", () => {
   const provider = anchor.AnchorProvider.env();
@@ -40,6 +57,23 @@ describe("bidx", () => {
   runCreateAuctionTests(() => ({ program, connection, platform }));
   runPlaceBidTests(() => ({ program, connection, platform }));
   runSettleAndWithdrawTests(() => ({ program, connection, platform }));
+  runSealedBidTests(() => ({ program, connection, platform }));
+  runBuyNowTests(() => ({ program, connection, platform }));
+  runMultiUnitTests(() => ({ program, connection, platform }));
+  runLotTests(() => ({ program, connection, platform }));
+  runHiddenReserveTests(() => ({ program, connection, platform }));
+  runCancelAuctionTests(() => ({ program, connection, platform }));
+  runPauseAuctionTests(() => ({ program, connection, platform }));
+  runAmendAuctionTests(() => ({ program, connection, platform }));
+  runAllowlistTests(() => ({ program, connection, platform }));
+  runTokenGateTests(() => ({ program, connection, platform }));
+  runBondTests(() => ({ program, connection, platform }));
+  runNativeSolTests(() => ({ program, connection, platform }));
+  runPaymentOptionsTests(() => ({ program, connection, platform }));
+  runOracleTests(() => ({ program, connection, platform }));
+  runToken2022Tests(() => ({ program, connection, platform }));
+  runPnftTests(() => ({ program, connection, platform }));
+  runCnftTests(() => ({ program, connection, platform }));
 
   after("close platform (reclaim rent)", async () => {
     if (!platform) return;
@@ -0,0 +1,251 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  createFundedTokenAccount,
+  fund,
+  getBidPDA,
+  setupDigitalNftAuction,
+  setupPlatform,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+const BOND = 1_000_000;
+
+export function getBondPDA(
+  bidder: PublicKey,
+  auction: PublicKey,
+  programId: PublicKey,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bond"), bidder.toBuffer(), auction.toBuffer()],
+    programId,
+  );
+}
+
+interface BondContext {
+  bidder: Keypair;
+  bidderTokenAccount: PublicKey;
+  bond: PublicKey;
+  bondVault: PublicKey;
+}
+
+export function runBondTests(getCtx: () => Ctx) {
+  describe("participation bonds", () => {
+    // PhysicalRWA auctions stay pending until authenticated; bonds can be posted meanwhile
+    async function setupBondedAuction(): Promise<AuctionContext> {
+      const { program, connection, platform } = getCtx();
+      return setupDigitalNftAuction(program, connection, platform, 0, {
+        assetType: { physicalRwa: {} },
+        participationBond: new BN(BOND),
+        activate: false,
+      });
+    }
+
+    async function postBond(auctionCtx: AuctionContext): Promise<BondContext> {
+      const { program, connection, platform } = getCtx();
+      const bidder = Keypair.generate();
+      await fund(connection, bidder.publicKey);
+
+      const bidderTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        bidder.publicKey,
+        BOND,
+      );
+      const [bond] = getBondPDA(
+        bidder.publicKey,
+        auctionCtx.auction,
+        program.programId,
+      );
+      const bondVault = getAssociatedTokenAddressSync(
+        platform.usdcMint,
+        auctionCtx.auction,
+        true,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+      await program.methods
+        .postBond(new BN(0))
+        .accounts({
+          bidder: bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bond,
+          platformConfig: platform.platformConfig,
+          bidderTokenAccount,
+          bondVault,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bidder])
+        .rpc();
+
+      return { bidder, bidderTokenAccount, bond, bondVault };
+    }
+
+    function withdrawBond(auctionCtx: AuctionContext, posted: BondContext) {
+      const { program, platform } = getCtx();
+      return program.methods
+        .withdrawBond(new BN(0))
+        .accounts({
+          bidder: posted.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bond: posted.bond,
+          bid: getBidPDA(
+            posted.bidder.publicKey,
+            auctionCtx.auction,
+            program.programId,
+          )[0],
+          bondVault: posted.bondVault,
+          bidderTokenAccount: posted.bidderTokenAccount,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([posted.bidder])
+        .rpc();
+    }
+
+    it("rejects bonds on digital auctions", async () => {
+      const { program, connection, platform } = getCtx();
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          participationBond: new BN(BOND),
+        }),
+        "InvalidParticipationBond",
+      );
+    });
+
+    it("locks the bond until the auction is over", async () => {
+      const { program, connection } = getCtx();
+      const auctionCtx = await setupBondedAuction();
+      const posted = await postBond(auctionCtx);
+
+      const vault = await getAccount(
+        connection,
+        posted.bondVault,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(vault.amount)).to.equal(BOND);
+      await assertAnchorError(withdrawBond(auctionCtx, posted), "BondLocked");
+
+      await program.methods
+        .cancelAuction(new BN(0), null)
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          lotManifest: null,
+          platformConfig: getCtx().platform.platformConfig,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          sellerNftAccount: auctionCtx.sellerNftAccount,
+          sellerTokenAccount: null,
+          treasury: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([auctionCtx.seller])
+        .rpc();
+
+      await withdrawBond(auctionCtx, posted);
+
+      const refunded = await getAccount(
+        connection,
+        posted.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(BOND);
+      expect(await connection.getAccountInfo(posted.bond)).to.be.null;
+    });
+
+    function forfeitBond(
+      auctionCtx: AuctionContext,
+      posted: BondContext,
+      reason: any,
+      platform: PlatformContext = getCtx().platform,
+    ) {
+      const { program } = getCtx();
+      return program.methods
+        .forfeitBond(new BN(0), reason)
+        .accounts({
+          admin: platform.admin.publicKey,
+          platformConfig: platform.platformConfig,
+          bidder: posted.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bond: posted.bond,
+          bid: getBidPDA(
+            posted.bidder.publicKey,
+            auctionCtx.auction,
+            program.programId,
+          )[0],
+          bondVault: posted.bondVault,
+          treasury: platform.treasuryUsdc,
+          tokenMint: getCtx().platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([platform.admin])
+        .rpc();
+    }
+
+    it("only forfeits a bond over an open bid or an abandoned lot", async () => {
+      const { connection } = getCtx();
+      const auctionCtx = await setupBondedAuction();
+      const posted = await postBond(auctionCtx);
+
+      // bonded but never bid, on an auction that hasn't opened
+      await assertAnchorError(
+        forfeitBond(auctionCtx, posted, { shillBidding: {} }),
+        "BondNotForfeitable",
+      );
+      await assertAnchorError(
+        forfeitBond(auctionCtx, posted, { abandonedLot: {} }),
+        "BondNotForfeitable",
+      );
+
+      const vault = await getAccount(
+        connection,
+        posted.bondVault,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(vault.amount)).to.equal(BOND);
+    });
+
+    it("rejects admins of other platform configs", async () => {
+      const { program, connection } = getCtx();
+      const auctionCtx = await setupBondedAuction();
+      const posted = await postBond(auctionCtx);
+      const otherPlatform = await setupPlatform(program, connection);
+
+      await assertAnchorError(
+        forfeitBond(auctionCtx, posted, { shillBidding: {} }, otherPlatform),
+        "WrongPlatformConfig",
+      );
+    });
+  });
+}
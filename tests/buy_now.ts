@@ -0,0 +1,173 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  fund,
+  assertAnchorError,
+  createFundedTokenAccount,
+  getBidPDA,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runBuyNowTests(getCtx: () => Ctx) {
+  describe("buy_now", () => {
+    async function buyNow(auction: PublicKey, buyer: Keypair, funds: number) {
+      const { program, connection, platform } = getCtx();
+      const buyerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        buyer.publicKey,
+        funds,
+      );
+      const [bid] = getBidPDA(buyer.publicKey, auction, program.programId);
+      const escrowVault = getAssociatedTokenAddressSync(
+        platform.usdcMint,
+        bid,
+        true,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+      return program.methods
+        .buyNow([])
+        .accounts({
+          buyer: buyer.publicKey,
+          bid,
+          auction,
+          platformConfig: platform.platformConfig,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          bond: null,
+          buyerTokenAccount,
+          escrowVault,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    it("buyer wins at the buy-it-now price and outbid bidders can withdraw", async () => {
+      const { program, connection, platform } = getCtx();
+      const buyNowPrice = new BN(10_000_000);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { buyNowPrice },
+      );
+
+      const outbid = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+
+      const buyer = Keypair.generate();
+      await fund(connection, buyer.publicKey);
+      await buyNow(auctionCtx.auction, buyer, buyNowPrice.toNumber());
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ ended: {} });
+      expect(auctionData.highestBid.toNumber()).to.equal(
+        buyNowPrice.toNumber(),
+      );
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        buyer.publicKey.toBase58(),
+      );
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: outbid.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: outbid.bid,
+          escrowVault: outbid.escrowVault,
+          bidderTokenAccount: outbid.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([outbid.bidder])
+        .rpc();
+    });
+
+    it("proxy leader escrowing more than the price buys it without topping up", async () => {
+      const { program, connection, platform } = getCtx();
+      const buyNowPrice = new BN(10_000_000);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { buyNowPrice },
+      );
+
+      // the proxy maximum sits above the price while the standing bid is below it
+      const leader = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(15_000_000),
+        true,
+      );
+      await buyNow(auctionCtx.auction, leader.bidder, 0);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ ended: {} });
+      expect(auctionData.highestBid.toNumber()).to.equal(
+        buyNowPrice.toNumber(),
+      );
+      // the excess stays escrowed until settlement refunds it
+      const bidData = await program.account.bid.fetch(leader.bid);
+      expect(bidData.amount.toNumber()).to.equal(15_000_000);
+    });
+
+    it("rejects buy-it-now on an auction without a price", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+
+      const buyer = Keypair.generate();
+      await fund(connection, buyer.publicKey);
+      await assertAnchorError(
+        buyNow(auctionCtx.auction, buyer, 10_000_000),
+        "BuyNowUnavailable",
+      );
+    });
+  });
+}
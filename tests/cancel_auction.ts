@@ -0,0 +1,315 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  createFundedTokenAccount,
+  createTokenMint,
+  endAuction,
+  setupBid,
+  setupCommit,
+  setupDigitalNftAuction,
+  MAX_AUCTION_DURATION,
+  MIN_AUCTION_DURATION,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+import { placeBidIn } from "./payment_options";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+const CANCELLATION_FEE_BPS = 500; // 5%
+
+export function runCancelAuctionTests(getCtx: () => Ctx) {
+  describe("cancel_auction", () => {
+    function cancel(
+      auctionCtx: AuctionContext,
+      sellerTokenAccount?: PublicKey,
+      feeMint: PublicKey = getCtx().platform.usdcMint,
+      treasury: PublicKey = getCtx().platform.treasuryUsdc,
+    ) {
+      const { program, platform } = getCtx();
+      return program.methods
+        .cancelAuction(new BN(0), null)
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          sellerNftAccount: auctionCtx.sellerNftAccount,
+          sellerTokenAccount: sellerTokenAccount ?? null,
+          treasury: sellerTokenAccount ? treasury : null,
+          tokenMint: sellerTokenAccount ? feeMint : null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([auctionCtx.seller])
+        .rpc();
+    }
+
+    function setCancellationFee(bps: number) {
+      const { program, platform } = getCtx();
+      return program.methods
+        .updatePlatformConfig(
+          null,
+          null,
+          MIN_AUCTION_DURATION,
+          MAX_AUCTION_DURATION,
+          bps,
+        )
+        .accounts({
+          admin: platform.admin.publicKey,
+          platformConfig: platform.platformConfig,
+        })
+        .signers([platform.admin])
+        .rpc();
+    }
+
+    it("seller cancels before the first bid and gets the item back", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+
+      await cancel(auctionCtx);
+
+      const item = await getAccount(
+        connection,
+        auctionCtx.sellerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(item.amount)).to.equal(1);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ cancelled: {} });
+    });
+
+    it("seller gets the item back from an auction that ended without bids", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      await cancel(auctionCtx);
+
+      const item = await getAccount(
+        connection,
+        auctionCtx.sellerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(item.amount)).to.equal(1);
+    });
+
+    it("rejects cancelling once bids exist without a cancellation fee", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+
+      await assertAnchorError(cancel(auctionCtx), "BidsExist");
+    });
+
+    describe("with a platform cancellation fee", () => {
+      before(async () => setCancellationFee(CANCELLATION_FEE_BPS));
+      after(async () => setCancellationFee(0));
+
+      it("seller pays the fee and bidders withdraw in full", async () => {
+        const { program, connection, platform } = getCtx();
+        const auctionCtx = await setupDigitalNftAuction(
+          program,
+          connection,
+          platform,
+        );
+        const bidder = await setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(6_000_000),
+        );
+
+        const sellerTokenAccount = await createFundedTokenAccount(
+          connection,
+          platform.admin,
+          platform.usdcMint,
+          auctionCtx.seller.publicKey,
+          1_000_000,
+        );
+        const treasuryBefore = await getAccount(
+          connection,
+          platform.treasuryUsdc,
+          undefined,
+          TOKEN_PROGRAM_ID,
+        );
+
+        await cancel(auctionCtx, sellerTokenAccount);
+
+        const treasuryAfter = await getAccount(
+          connection,
+          platform.treasuryUsdc,
+          undefined,
+          TOKEN_PROGRAM_ID,
+        );
+        expect(
+          Number(treasuryAfter.amount) - Number(treasuryBefore.amount),
+        ).to.equal((6_000_000 * CANCELLATION_FEE_BPS) / 10_000);
+
+        await program.methods
+          .withdrawBid(new BN(0))
+          .accounts({
+            bidder: bidder.bidder.publicKey,
+            seller: auctionCtx.seller.publicKey,
+            auction: auctionCtx.auction,
+            bid: bidder.bid,
+            escrowVault: bidder.escrowVault,
+            bidderTokenAccount: bidder.bidderTokenAccount,
+            unitBook: null,
+            sellerTokenAccount: null,
+            bond: null,
+            bondVault: null,
+            tokenMint: platform.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bidder.bidder])
+          .rpc();
+
+        // setupBid funds 10x the bid, all of it back in the bidder's account
+        const refunded = await getAccount(
+          connection,
+          bidder.bidderTokenAccount,
+          undefined,
+          TOKEN_PROGRAM_ID,
+        );
+        expect(Number(refunded.amount)).to.equal(60_000_000);
+      });
+
+      it("pays the fee into the platform's vault for the auction's mint", async () => {
+        const { program, connection, platform } = getCtx();
+        const usdt = await createTokenMint(connection, platform.admin, 6);
+        const treasuryUsdt = getAssociatedTokenAddressSync(
+          usdt,
+          platform.platformConfig,
+          true,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        );
+        await program.methods
+          .openTreasuryVault()
+          .accounts({
+            admin: platform.admin.publicKey,
+            platformConfig: platform.platformConfig,
+            treasuryVault: treasuryUsdt,
+            tokenMint: usdt,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([platform.admin])
+          .rpc();
+
+        const auctionCtx = await setupDigitalNftAuction(
+          program,
+          connection,
+          platform,
+          0,
+          { acceptedToken: usdt },
+        );
+        await placeBidIn(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          usdt,
+          new BN(6_000_000),
+        );
+
+        const sellerTokenAccount = await createFundedTokenAccount(
+          connection,
+          platform.admin,
+          usdt,
+          auctionCtx.seller.publicKey,
+          1_000_000,
+        );
+        await cancel(auctionCtx, sellerTokenAccount, usdt, treasuryUsdt);
+
+        const treasury = await getAccount(
+          connection,
+          treasuryUsdt,
+          undefined,
+          TOKEN_PROGRAM_ID,
+        );
+        expect(Number(treasury.amount)).to.equal(
+          (6_000_000 * CANCELLATION_FEE_BPS) / 10_000,
+        );
+      });
+
+      it("rejects cancelling a sealed-bid auction with committed bids", async () => {
+        const { program, connection, platform } = getCtx();
+        const auctionCtx = await setupDigitalNftAuction(
+          program,
+          connection,
+          platform,
+          0,
+          { sealedBid: { revealWindowSecs: 20, penaltyBps: 1_000 } },
+        );
+        await setupCommit(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(2_000_000),
+        );
+
+        const sellerTokenAccount = await createFundedTokenAccount(
+          connection,
+          platform.admin,
+          platform.usdcMint,
+          auctionCtx.seller.publicKey,
+          1_000_000,
+        );
+        await assertAnchorError(
+          cancel(auctionCtx, sellerTokenAccount),
+          "BidsExist",
+        );
+      });
+    });
+  });
+}
@@ -0,0 +1,396 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  ComputeBudgetProgram,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  airdrop,
+  assertAnchorError,
+  auctionTerms,
+  createFundedTokenAccount,
+  getAuctionPDA,
+  getAuthenticationPDA,
+  getSellerStatePDA,
+  setupBid,
+  endAuction,
+  waitForUnixTimestamp,
+  PlatformContext,
+  TEST_AUCTION_START_DELAY_SECS,
+  TEST_AUCTION_DURATION_SECS,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+// cloned from mainnet into the local validator by Anchor.toml
+const BUBBLEGUM_PROGRAM_ID = new PublicKey(
+  "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY",
+);
+const COMPRESSION_PROGRAM_ID = new PublicKey(
+  "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK",
+);
+const NOOP_PROGRAM_ID = new PublicKey(
+  "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV",
+);
+
+const MAX_DEPTH = 3;
+const MAX_BUFFER_SIZE = 8;
+
+const computeBudget = ComputeBudgetProgram.setComputeUnitLimit({
+  units: 400_000,
+});
+
+const meta = (
+  pubkey: PublicKey,
+  isWritable: boolean,
+  isSigner = false,
+): AccountMeta => ({ pubkey, isSigner, isWritable });
+
+const keccak = (...parts: Uint8Array[]) =>
+  Buffer.from(keccak_256(Buffer.concat(parts)));
+
+function u32(value: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(value);
+  return buf;
+}
+
+function u64(value: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(value));
+  return buf;
+}
+
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value);
+  return Buffer.concat([u32(bytes.length), bytes]);
+}
+
+const treeConfigPDA = (tree: PublicKey) =>
+  PublicKey.findProgramAddressSync([tree.toBuffer()], BUBBLEGUM_PROGRAM_ID)[0];
+
+const assetId = (tree: PublicKey, nonce: number) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("asset"), tree.toBuffer(), u64(nonce)],
+    BUBBLEGUM_PROGRAM_ID,
+  )[0];
+
+// header, then sequence number, active index and buffer size, the change
+// log buffer and the rightmost proof, with no canopy
+const TREE_ACCOUNT_SIZE =
+  56 +
+  24 +
+  MAX_BUFFER_SIZE * (32 + 32 * MAX_DEPTH + 8) +
+  (32 * MAX_DEPTH + 32 + 8);
+
+// proof for leaf 0 of a tree holding nothing else: the empty subtree roots
+const EMPTY_PROOF = Array.from({ length: MAX_DEPTH }).reduce<Buffer[]>(
+  (nodes, _, level) =>
+    nodes.concat(
+      level === 0 ? Buffer.alloc(32) : keccak(nodes[level - 1], nodes[level - 1]),
+    ),
+  [],
+);
+
+function leafRoot(
+  tree: PublicKey,
+  owner: PublicKey,
+  dataHash: Buffer,
+  creatorHash: Buffer,
+): Buffer {
+  const leaf = keccak(
+    Buffer.from([1]), // LeafSchema::V1
+    assetId(tree, 0).toBuffer(),
+    owner.toBuffer(),
+    owner.toBuffer(), // delegate
+    u64(0),
+    dataHash,
+    creatorHash,
+  );
+  return EMPTY_PROOF.reduce((node, sibling) => keccak(node, sibling), leaf);
+}
+
+/** The tree's current root, out of its active change log. */
+async function currentRoot(
+  connection: anchor.web3.Connection,
+  tree: PublicKey,
+): Promise<Buffer> {
+  const { data } = await connection.getAccountInfo(tree);
+  const activeIndex = Number(data.readBigUInt64LE(64));
+  const offset = 80 + activeIndex * (32 + 32 * MAX_DEPTH + 8);
+  return data.subarray(offset, offset + 32);
+}
+
+/**
+ * Creates a small Bubblegum tree and mints one compressed NFT to `owner`,
+ * returning the hashes bidx needs to prove the leaf.
+ */
+async function mintCompressedNft(
+  connection: anchor.web3.Connection,
+  payer: Keypair,
+  owner: PublicKey,
+): Promise<{ tree: PublicKey; dataHash: Buffer; creatorHash: Buffer }> {
+  const tree = Keypair.generate();
+  const treeConfig = treeConfigPDA(tree.publicKey);
+
+  const allocTree = SystemProgram.createAccount({
+    fromPubkey: payer.publicKey,
+    newAccountPubkey: tree.publicKey,
+    lamports: await connection.getMinimumBalanceForRentExemption(
+      TREE_ACCOUNT_SIZE,
+    ),
+    space: TREE_ACCOUNT_SIZE,
+    programId: COMPRESSION_PROGRAM_ID,
+  });
+  const createTree = new TransactionInstruction({
+    programId: BUBBLEGUM_PROGRAM_ID,
+    keys: [
+      meta(treeConfig, true),
+      meta(tree.publicKey, true),
+      meta(payer.publicKey, true, true),
+      meta(payer.publicKey, false, true), // tree creator
+      meta(NOOP_PROGRAM_ID, false),
+      meta(COMPRESSION_PROGRAM_ID, false),
+      meta(SystemProgram.programId, false),
+    ],
+    data: Buffer.concat([
+      Buffer.from([165, 83, 136, 142, 89, 202, 47, 220]),
+      u32(MAX_DEPTH),
+      u32(MAX_BUFFER_SIZE),
+      Buffer.from([0]), // public: None
+    ]),
+  });
+
+  const sellerFeeBps = Buffer.alloc(2);
+  sellerFeeBps.writeUInt16LE(500);
+  const metadataArgs = Buffer.concat([
+    borshString("Bidx cNFT"),
+    borshString("BCNFT"),
+    borshString("https://example.com/cnft.json"),
+    sellerFeeBps,
+    Buffer.from([
+      0, // primary_sale_happened
+      1, // is_mutable
+      0, // edition_nonce: None
+      1, 0, // token_standard: Some(NonFungible)
+      0, // collection: None
+      0, // uses: None
+      0, // token_program_version: Original
+    ]),
+    u32(0), // creators: []
+  ]);
+  const mintV1 = new TransactionInstruction({
+    programId: BUBBLEGUM_PROGRAM_ID,
+    keys: [
+      meta(treeConfig, true),
+      meta(owner, false),
+      meta(owner, false), // leaf delegate
+      meta(tree.publicKey, true),
+      meta(payer.publicKey, true, true),
+      meta(payer.publicKey, false, true), // tree delegate
+      meta(NOOP_PROGRAM_ID, false),
+      meta(COMPRESSION_PROGRAM_ID, false),
+      meta(SystemProgram.programId, false),
+    ],
+    data: Buffer.concat([
+      Buffer.from([145, 98, 192, 118, 184, 147, 118, 104]),
+      metadataArgs,
+    ]),
+  });
+
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(allocTree, createTree),
+    [payer, tree],
+  );
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(computeBudget, mintV1),
+    [payer],
+  );
+
+  return {
+    tree: tree.publicKey,
+    dataHash: keccak(keccak(metadataArgs), sellerFeeBps),
+    creatorHash: keccak(),
+  };
+}
+
+/**
+ * Remaining accounts bidx takes for a compressed NFT transfer, see
+ * bubblegum::LeafTransfer.
+ */
+function compressedAccounts(tree: PublicKey): AccountMeta[] {
+  return [
+    meta(BUBBLEGUM_PROGRAM_ID, false),
+    meta(treeConfigPDA(tree), false),
+    meta(tree, true),
+    meta(NOOP_PROGRAM_ID, false),
+    meta(COMPRESSION_PROGRAM_ID, false),
+    ...EMPTY_PROOF.map((node) => meta(new PublicKey(node), false)),
+  ];
+}
+
+export function runCnftTests(getCtx: () => Ctx) {
+  describe("cnft", () => {
+    before(async function () {
+      const { connection } = getCtx();
+      // Bubblegum only exists on a validator that cloned it
+      if (!(await connection.getAccountInfo(BUBBLEGUM_PROGRAM_ID))) {
+        this.skip();
+      }
+    });
+
+    it("holds a compressed NFT leaf in custody and settles it to the winner", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const seller = Keypair.generate();
+      await airdrop(connection, seller.publicKey);
+      const { tree, dataHash, creatorHash } = await mintCompressedNft(
+        connection,
+        platform.admin,
+        seller.publicKey,
+      );
+
+      const [sellerState] = getSellerStatePDA(
+        seller.publicKey,
+        program.programId,
+      );
+      const [auction] = getAuctionPDA(seller.publicKey, 0, program.programId);
+      const [authentication] = getAuthenticationPDA(auction, program.programId);
+
+      const now = Math.floor(Date.now() / 1000);
+      const startDate = new BN(now + TEST_AUCTION_START_DELAY_SECS);
+      const endDate = new BN(now + TEST_AUCTION_DURATION_SECS);
+      await program.methods
+        .createAuction(
+          platform.usdcMint,
+          new BN(1_000_000),
+          new BN(5_000_000),
+          startDate,
+          endDate,
+          null,
+          { digitalNft: {} },
+          { english: {} },
+          auctionTerms(),
+          {
+            root: [...leafRoot(tree, seller.publicKey, dataHash, creatorHash)],
+            dataHash: [...dataHash],
+            creatorHash: [...creatorHash],
+            leafIndex: 0,
+          },
+        )
+        .accounts({
+          seller: seller.publicKey,
+          sellerState,
+          auction,
+          nftMint: null,
+          nftMetadata: null,
+          sellerNftAccount: null,
+          tokenMint: platform.usdcMint,
+          itemVault: null,
+          authentication,
+          registry: platform.authenticatorsRegistry,
+          platformConfig: platform.platformConfig,
+          lotManifest: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(compressedAccounts(tree))
+        .preInstructions([computeBudget])
+        .signers([seller])
+        .rpc();
+
+      let auctionData = await program.account.auction.fetch(auction);
+      expect(auctionData.nftMint.toBase58()).to.equal(
+        assetId(tree, 0).toBase58(),
+      );
+      expect(auctionData.itemCustody.compressed.tree.toBase58()).to.equal(
+        tree.toBase58(),
+      );
+      const heldRoot = leafRoot(tree, auction, dataHash, creatorHash);
+      expect(await currentRoot(connection, tree)).to.deep.equal(heldRoot);
+
+      await waitForUnixTimestamp(startDate.toNumber());
+      await program.methods
+        .activateAuction(new BN(0))
+        .accounts({ seller: seller.publicKey, auction })
+        .rpc();
+
+      const winner = await setupBid(
+        program,
+        connection,
+        platform,
+        auction,
+        new BN(6_000_000),
+      );
+      await endAuction(program, auction, seller.publicKey, 0);
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        seller.publicKey,
+        0,
+      );
+
+      const settle = (leafProof: { root: number[]; proofLen: number } | null) =>
+        program.methods
+          .settleAuction(new BN(0), leafProof)
+          .accounts({
+            winner: winner.bidder.publicKey,
+            seller: seller.publicKey,
+            authenticator: Keypair.generate().publicKey,
+            auction,
+            bid: winner.bid,
+            authentication: null,
+            unitBook: null,
+            lotManifest: null,
+            platformConfig: platform.platformConfig,
+            priceUpdate: null,
+            escrowVault: winner.escrowVault,
+            sellerTokenAccount,
+            treasury: platform.treasuryUsdc,
+            authenticatorTokenAccount: null,
+            nftMint: null,
+            itemVault: null,
+            winnerNftAccount: null,
+            winnerTokenAccount: null,
+            bond: null,
+            bondVault: null,
+            tokenMint: platform.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(compressedAccounts(tree))
+          .preInstructions([computeBudget])
+          .signers([winner.bidder])
+          .rpc();
+
+      // the leaf can't move without the root its proof was taken against
+      await assertAnchorError(settle(null), "CompressedAccountsRequired");
+
+      await settle({ root: [...heldRoot], proofLen: MAX_DEPTH });
+
+      expect(await currentRoot(connection, tree)).to.deep.equal(
+        leafRoot(tree, winner.bidder.publicKey, dataHash, creatorHash),
+      );
+      auctionData = await program.account.auction.fetch(auction);
+      expect(auctionData.auctionStatus).to.deep.equal({ settled: {} });
+    });
+  });
+}
@@ -18,11 +18,14 @@ import { Bidx } from "../target/types/bidx";
 import {
   fund,
   assertAnchorError,
+  auctionTerms,
   createNftMint,
   mintNftToSeller,
   getAuctionPDA,
   getAuthenticationPDA,
   getSellerStatePDA,
+  MAX_AUCTION_DURATION,
+  setupPlatform,
   PlatformContext,
 } from "./helpers";
 
@@ -122,15 +125,23 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
           new BN(now + 60 * 60),
           null,
           { digitalNft: {} },
+          { english: {} },
+          auctionTerms(),
+          null,
         )
         .accounts({
           seller: seller.publicKey,
           sellerState,
           auction,
           nftMint,
+          nftMetadata: null,
+          sellerNftAccount: null,
+          tokenMint: platform.usdcMint,
           itemVault,
           authentication,
           registry: platform.authenticatorsRegistry,
+          platformConfig: platform.platformConfig,
+          lotManifest: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([seller])
@@ -146,7 +157,7 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
       expect(auctionData.reservedPrice.toNumber()).to.equal(
         reservedPrice.toNumber(),
       );
-      expect(auctionData.auctionStatus).to.deep.equal({ active: {} });
+      expect(auctionData.auctionStatus).to.deep.equal({ pending: {} });
       expect(auctionData.authStatus).to.deep.equal({ notRequired: {} });
       expect(auctionData.highestBid.toNumber()).to.equal(0);
       expect(auctionData.acceptedToken.toBase58()).to.equal(
@@ -157,6 +168,15 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
         sellerState,
       );
       expect(sellerStateData.auctionCount.toNumber()).to.equal(1);
+
+      // start_date is a minute out, so the activation crank must wait
+      await assertAnchorError(
+        program.methods
+          .activateAuction(new BN(0))
+          .accounts({ seller: seller.publicKey, auction })
+          .rpc(),
+        "NotStarted",
+      );
     });
 
     // error paths
@@ -180,15 +200,23 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
             new BN(now + 60 * 60),
             null,
             { digitalNft: {} },
+            { english: {} },
+            auctionTerms(),
+            null,
           )
           .accounts({
             seller: seller.publicKey,
             sellerState,
             auction,
             nftMint,
+            nftMetadata: null,
+            sellerNftAccount: null,
+            tokenMint: platform.usdcMint,
             itemVault,
             authentication,
             registry: platform.authenticatorsRegistry,
+            platformConfig: platform.platformConfig,
+            lotManifest: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([seller])
@@ -217,15 +245,23 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
             new BN(now + 60), // end = 1min — before start
             null,
             { digitalNft: {} },
+            { english: {} },
+            auctionTerms(),
+            null,
           )
           .accounts({
             seller: seller.publicKey,
             sellerState,
             auction,
             nftMint,
+            nftMetadata: null,
+            sellerNftAccount: null,
+            tokenMint: platform.usdcMint,
             itemVault,
             authentication,
             registry: platform.authenticatorsRegistry,
+            platformConfig: platform.platformConfig,
+            lotManifest: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([seller])
@@ -254,15 +290,23 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
             new BN(now + 60 * 60),
             null,
             { digitalNft: {} },
+            { english: {} },
+            auctionTerms(),
+            null,
           )
           .accounts({
             seller: seller.publicKey,
             sellerState,
             auction,
             nftMint,
+            nftMetadata: null,
+            sellerNftAccount: null,
+            tokenMint: platform.usdcMint,
             itemVault,
             authentication,
             registry: platform.authenticatorsRegistry,
+            platformConfig: platform.platformConfig,
+            lotManifest: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([seller])
@@ -270,5 +314,157 @@ export function runCreateAuctionTests(getCtx: () => Ctx) {
         "ReservedPriceTooLow",
       );
     });
+
+    it("rejects durations outside the platform's limits", async () => {
+      const { program, connection, platform } = getCtx();
+      const seller = Keypair.generate();
+      await fund(connection, seller.publicKey);
+
+      const { nftMint, sellerState, auction, authentication, itemVault } =
+        await buildAuctionAccounts(program, connection, platform, seller);
+
+      const now = Math.floor(Date.now() / 1000);
+
+      await assertAnchorError(
+        program.methods
+          .createAuction(
+            platform.usdcMint,
+            new BN(1_000_000),
+            new BN(5_000_000),
+            new BN(now + 60),
+            new BN(now + 60).add(MAX_AUCTION_DURATION).addn(1),
+            null,
+            { digitalNft: {} },
+            { english: {} },
+            auctionTerms(),
+            null,
+          )
+          .accounts({
+            seller: seller.publicKey,
+            sellerState,
+            auction,
+            nftMint,
+            nftMetadata: null,
+            sellerNftAccount: null,
+            tokenMint: platform.usdcMint,
+            itemVault,
+            authentication,
+            registry: platform.authenticatorsRegistry,
+            platformConfig: platform.platformConfig,
+            lotManifest: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([seller])
+          .rpc(),
+        "InvalidDuration",
+      );
+    });
+
+    it("rejects new auctions while the platform is paused", async () => {
+      const { program, connection, platform } = getCtx();
+      const seller = Keypair.generate();
+      await fund(connection, seller.publicKey);
+
+      const { nftMint, sellerState, auction, authentication, itemVault } =
+        await buildAuctionAccounts(program, connection, platform, seller);
+
+      const now = Math.floor(Date.now() / 1000);
+
+      const togglePause = () =>
+        program.methods
+          .togglePausePlatform()
+          .accounts({
+            admin: platform.admin.publicKey,
+            platformConfig: platform.platformConfig,
+          })
+          .signers([platform.admin])
+          .rpc();
+
+      await togglePause();
+      try {
+        await assertAnchorError(
+          program.methods
+            .createAuction(
+              platform.usdcMint,
+              new BN(1_000_000),
+              new BN(5_000_000),
+              new BN(now + 60),
+              new BN(now + 60 * 60),
+              null,
+              { digitalNft: {} },
+              { english: {} },
+              auctionTerms(),
+              null,
+            )
+            .accounts({
+              seller: seller.publicKey,
+              sellerState,
+              auction,
+              nftMint,
+              nftMetadata: null,
+              sellerNftAccount: null,
+              tokenMint: platform.usdcMint,
+              itemVault,
+              authentication,
+              registry: platform.authenticatorsRegistry,
+              platformConfig: platform.platformConfig,
+              lotManifest: null,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([seller])
+            .rpc(),
+          "PlatformPaused",
+        );
+      } finally {
+        await togglePause();
+      }
+    });
+
+    it("rejects another platform's authenticators registry", async () => {
+      const { program, connection, platform } = getCtx();
+      const seller = Keypair.generate();
+      await fund(connection, seller.publicKey);
+      const other = await setupPlatform(program, connection, [
+        seller.publicKey,
+      ]);
+
+      const { nftMint, sellerState, auction, authentication, itemVault } =
+        await buildAuctionAccounts(program, connection, platform, seller);
+
+      const now = Math.floor(Date.now() / 1000);
+      await assertAnchorError(
+        program.methods
+          .createAuction(
+            platform.usdcMint,
+            new BN(1_000_000),
+            new BN(5_000_000),
+            new BN(now + 60),
+            new BN(now + 60 * 60),
+            null,
+            { physicalRwa: {} },
+            { english: {} },
+            auctionTerms(),
+            null,
+          )
+          .accounts({
+            seller: seller.publicKey,
+            sellerState,
+            auction,
+            nftMint,
+            nftMetadata: null,
+            sellerNftAccount: null,
+            tokenMint: platform.usdcMint,
+            itemVault,
+            authentication,
+            registry: other.authenticatorsRegistry,
+            platformConfig: platform.platformConfig,
+            lotManifest: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([seller])
+          .rpc(),
+        "ConstraintSeeds",
+      );
+    });
   });
 }
@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+# Dumps the mainnet programs the tests need, so Anchor.toml can load them with
+# [[test.genesis]] instead of cloning them. Re-run to pick up upgrades, then
+# commit the .so files next to this script.
+set -euo pipefail
+cd "$(dirname "$0")"
+
+solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata.so
+solana program dump -u m BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY mpl_bubblegum.so
+solana program dump -u m cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK spl_account_compression.so
+solana program dump -u m noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV spl_noop.so
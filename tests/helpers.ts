@@ -2,6 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import * as fs from "fs";
 import * as path from "path";
+import { createHash } from "crypto";
 import {
   Keypair,
   PublicKey,
@@ -18,6 +19,7 @@ import {
   getOrCreateAssociatedTokenAccount,
   transfer,
   createAssociatedTokenAccountInstruction,
+  createTransferCheckedInstruction,
 } from "@solana/spl-token";
 import { Transaction, sendAndConfirmTransaction } from "@solana/web3.js";
 import { Bidx } from "../target/types/bidx";
@@ -25,10 +27,10 @@ import { Bidx } from "../target/types/bidx";
 // CONSTANTS
 export const PLATFORM_FEE_BPS = 250; // 2.5%
 export const AUTH_FEE_BPS = 100; // 1%
-export const MIN_AUCTION_DURATION = new BN(60); // 1 min (seconds)
+export const MIN_AUCTION_DURATION = new BN(5); // 5 seconds, below the test auction duration
 export const MAX_AUCTION_DURATION = new BN(60 * 60 * 24 * 7); // 1 week
 export const TEST_AUCTION_START_DELAY_SECS = 1;
-export const TEST_AUCTION_DURATION_SECS = 2;
+export const TEST_AUCTION_DURATION_SECS = 10;
 export const DEVNET_AUCTION_START_DELAY_SECS = 20;
 export const DEVNET_AUCTION_DURATION_SECS = 60;
 
@@ -98,6 +100,16 @@ export function getBidPDA(
 }
 
 // AIRDROP HELPER
+export function getLotManifestPDA(
+  auction: PublicKey,
+  programId: PublicKey,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("lot"), auction.toBuffer()],
+    programId,
+  );
+}
+
 export async function airdrop(
   connection: anchor.web3.Connection,
   wallet: PublicKey,
@@ -215,13 +227,14 @@ export async function createNftMint(
 }
 
 /**
- * Creates an ATA for an NFT, mints 1 token into it (seller holds NFT pre-auction)
+ * Creates an ATA for an NFT, mints `units` tokens into it (seller holds NFT pre-auction)
  */
 export async function mintNftToSeller(
   connection: anchor.web3.Connection,
   payer: Keypair,
   nftMint: PublicKey,
   seller: PublicKey,
+  units: number = 1,
 ): Promise<PublicKey> {
   const ata = await getOrCreateAssociatedTokenAccount(
     connection,
@@ -241,7 +254,7 @@ export async function mintNftToSeller(
     nftMint,
     ata.address,
     payer,
-    1,
+    units,
     [],
     undefined,
     TOKEN_PROGRAM_ID,
@@ -347,6 +360,68 @@ export interface AuctionContext {
   endDate: BN;
   startingBid: BN;
   reservedPrice: BN;
+  lotItems: LotItemContext[];
+}
+
+export interface LotItemContext {
+  mint: PublicKey;
+  vault: PublicKey;
+}
+
+export interface SoftCloseOptions {
+  window: BN;
+  extension: BN;
+  maxExtensions: number | null;
+}
+
+export interface SealedBidOptions {
+  revealWindowSecs: number; // reveal phase length after end_date
+  penaltyBps: number;
+  pricing?: any; // defaults to { firstPrice: {} }
+}
+
+export interface AuctionOptions {
+  softClose?: SoftCloseOptions;
+  auctionType?: any;
+  sealedBid?: SealedBidOptions;
+  buyNowPrice?: BN;
+  minIncrement?: any;
+  multiUnit?: { quantity: BN; pricing: any };
+  lotSize?: number; // extra NFTs bundled with the primary item
+  hiddenReserve?: HiddenReserveOptions;
+  activate?: boolean; // crank Pending -> Active once start_date passes (default true)
+  allowlistRoot?: number[]; // see merkleRoot
+  bidderGate?: any;
+  assetType?: any; // defaults to digitalNft
+  participationBond?: BN;
+  acceptedToken?: PublicKey; // defaults to platform.usdcMint; SystemProgram id for native SOL
+  paymentOptions?: { mint: PublicKey; rate: BN }[]; // rate per RATE_SCALE units
+  usdPricing?: any; // starting and reserve prices in USD, priced by a Pyth feed
+}
+
+export interface HiddenReserveOptions {
+  reserve: BN;
+  salt: Buffer;
+  revealWindowSecs: number; // reveal deadline after end_date
+}
+
+/**
+ * create_auction's optional terms, all unset unless overridden.
+ */
+export function auctionTerms(overrides: Record<string, any> = {}) {
+  return {
+    softClose: null,
+    buyNowPrice: null,
+    minIncrement: null,
+    multiUnit: null,
+    hiddenReserve: null,
+    allowlistRoot: null,
+    bidderGate: null,
+    participationBond: null,
+    paymentOptions: [],
+    usdPricing: null,
+    ...overrides,
+  };
 }
 
 /**
@@ -358,6 +433,7 @@ export async function setupDigitalNftAuction(
   connection: anchor.web3.Connection,
   platform: PlatformContext,
   auctionCount: number = 0,
+  opts: AuctionOptions = {},
 ): Promise<AuctionContext> {
   const seller = Keypair.generate();
   await airdrop(connection, seller.publicKey);
@@ -365,11 +441,13 @@ export async function setupDigitalNftAuction(
   const nftMint = await createNftMint(connection, platform.admin);
 
   // Mint NFT to seller
+  const units = opts.multiUnit ? opts.multiUnit.quantity.toNumber() : 1;
   const sellerNftAccount = await mintNftToSeller(
     connection,
     platform.admin,
     nftMint,
     seller.publicKey,
+    units,
   );
 
   // item_vault = ATA of nftMint owned by auction PDA
@@ -407,7 +485,7 @@ export async function setupDigitalNftAuction(
       nftMint,
       itemVault, // to
       seller.publicKey,
-      1,
+      units,
       0,
       [],
       TOKEN_PROGRAM_ID,
@@ -417,6 +495,50 @@ export async function setupDigitalNftAuction(
   const tx = new Transaction().add(createVaultIx, transferNftIx);
   await sendAndConfirmTransaction(connection, tx, [seller]);
 
+  // Extra lot items are deposited the same way, one auction-owned vault each
+  const lotItems: LotItemContext[] = [];
+  for (let i = 0; i < (opts.lotSize ?? 0); i++) {
+    const mint = await createNftMint(connection, platform.admin);
+    const sellerAccount = await mintNftToSeller(
+      connection,
+      platform.admin,
+      mint,
+      seller.publicKey,
+    );
+    const vault = getAssociatedTokenAddressSync(
+      mint,
+      auction,
+      true,
+      TOKEN_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    );
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        createAssociatedTokenAccountInstruction(
+          seller.publicKey,
+          vault,
+          auction,
+          mint,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+        createTransferCheckedInstruction(
+          sellerAccount,
+          mint,
+          vault,
+          seller.publicKey,
+          1,
+          0,
+          [],
+          TOKEN_PROGRAM_ID,
+        ),
+      ),
+      [seller],
+    );
+    lotItems.push({ mint, vault });
+  }
+
   const now = Math.floor(Date.now() / 1000);
   const isDev = isDevnet(connection);
   const startDelay = isDev
@@ -428,31 +550,90 @@ export async function setupDigitalNftAuction(
   const startDate = new BN(now + startDelay);
   const endDate = new BN(now + duration);
   const startingBid = new BN(1_000_000);
-  const reservedPrice = new BN(5_000_000);
+  // a hidden reserve keeps the public reserve at zero
+  const reservedPrice = opts.hiddenReserve ? new BN(0) : new BN(5_000_000);
+  const auctionType = opts.sealedBid
+    ? {
+        sealedBid: {
+          revealEndDate: endDate.addn(opts.sealedBid.revealWindowSecs),
+          penaltyBps: opts.sealedBid.penaltyBps,
+          pricing: opts.sealedBid.pricing ?? { firstPrice: {} },
+        },
+      }
+    : opts.auctionType ?? { english: {} };
 
+  const acceptedToken = opts.acceptedToken ?? platform.usdcMint;
   await program.methods
     .createAuction(
-      platform.usdcMint,
+      acceptedToken,
       startingBid,
       reservedPrice,
       startDate,
       endDate,
       null,
-      { digitalNft: {} },
+      opts.assetType ?? { digitalNft: {} },
+      auctionType,
+      auctionTerms({
+        softClose: opts.softClose ?? null,
+        buyNowPrice: opts.buyNowPrice ?? null,
+        minIncrement: opts.minIncrement ?? null,
+        multiUnit: opts.multiUnit ?? null,
+        hiddenReserve: opts.hiddenReserve
+          ? {
+              commitment: reserveCommitment(
+                opts.hiddenReserve.reserve,
+                opts.hiddenReserve.salt,
+                auction,
+              ),
+              revealDeadline: endDate.addn(
+                opts.hiddenReserve.revealWindowSecs,
+              ),
+            }
+          : null,
+        allowlistRoot: opts.allowlistRoot ?? null,
+        bidderGate: opts.bidderGate ?? null,
+        participationBond: opts.participationBond ?? null,
+        paymentOptions: opts.paymentOptions ?? [],
+        usdPricing: opts.usdPricing ?? null,
+      }),
+      null,
     )
     .accounts({
       seller: seller.publicKey,
       sellerState,
       auction,
       nftMint,
+      nftMetadata: null,
+      sellerNftAccount: null,
+      tokenMint: acceptedToken.equals(SystemProgram.programId)
+        ? null
+        : acceptedToken,
       itemVault,
       authentication,
       registry: platform.authenticatorsRegistry,
+      platformConfig: platform.platformConfig,
+      lotManifest: lotItems.length
+        ? getLotManifestPDA(auction, program.programId)[0]
+        : null,
       systemProgram: SystemProgram.programId,
     })
+    .remainingAccounts(
+      lotItems.flatMap((item) => [
+        { pubkey: item.mint, isSigner: false, isWritable: false },
+        { pubkey: item.vault, isSigner: false, isWritable: false },
+      ]),
+    )
     .signers([seller])
     .rpc();
 
+  if (opts.activate ?? true) {
+    await waitForUnixTimestamp(startDate.toNumber());
+    await program.methods
+      .activateAuction(new BN(auctionCount))
+      .accounts({ seller: seller.publicKey, auction })
+      .rpc();
+  }
+
   return {
     seller,
     nftMint,
@@ -465,6 +646,7 @@ export async function setupDigitalNftAuction(
     endDate,
     startingBid,
     reservedPrice,
+    lotItems,
   };
 }
 
@@ -485,8 +667,11 @@ export async function setupBid(
   platform: PlatformContext,
   auctionPDA: PublicKey,
   bidAmount: BN,
+  proxy: boolean = false, // escrow bidAmount as a proxy maximum
+  bidder: Keypair = Keypair.generate(),
+  allowlistProof: number[][] = [],
+  gate: { tokenAccount: PublicKey; metadata: PublicKey | null } | null = null,
 ): Promise<BidContext> {
-  const bidder = Keypair.generate();
   await airdrop(connection, bidder.publicKey);
 
   // Fund bidder with USDC
@@ -508,12 +693,178 @@ export async function setupBid(
     ASSOCIATED_TOKEN_PROGRAM_ID,
   );
 
+  const method = proxy
+    ? program.methods.placeProxyBid(bidAmount, allowlistProof)
+    : program.methods.placeBid(bidAmount, allowlistProof);
+  await method
+    .accounts({
+      bidder: bidder.publicKey,
+      bid,
+      auction: auctionPDA,
+      platformConfig: platform.platformConfig,
+      priceUpdate: null,
+      gateTokenAccount: gate?.tokenAccount ?? null,
+      gateMetadata: gate?.metadata ?? null,
+      bond: null,
+      bidderTokenAccount,
+      escrowVault,
+      tokenMint: platform.usdcMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([bidder])
+    .rpc();
+
+  return {
+    bidder,
+    bidderTokenAccount,
+    escrowVault,
+    bid,
+    bidAmount,
+  };
+}
+
+/**
+ * sha256(amount_le || salt || bidder), matching Bid::sealed_commitment on-chain.
+ */
+export function sealedCommitment(
+  amount: BN,
+  salt: Buffer,
+  bidder: PublicKey,
+): number[] {
+  const digest = createHash("sha256")
+    .update(amount.toArrayLike(Buffer, "le", 8))
+    .update(salt)
+    .update(bidder.toBuffer())
+    .digest();
+  return Array.from(digest);
+}
+
+/**
+ * sha256(reserve_le || salt || auction), matching Auction::reserve_commitment on-chain.
+ */
+export function reserveCommitment(
+  reserve: BN,
+  salt: Buffer,
+  auction: PublicKey,
+): number[] {
+  const digest = createHash("sha256")
+    .update(reserve.toArrayLike(Buffer, "le", 8))
+    .update(salt)
+    .update(auction.toBuffer())
+    .digest();
+  return Array.from(digest);
+}
+
+const merkleLeaf = (bidder: PublicKey): Buffer =>
+  createHash("sha256")
+    .update(Buffer.from([0]))
+    .update(bidder.toBuffer())
+    .digest();
+
+const merkleParent = (a: Buffer, b: Buffer): Buffer => {
+  const [lo, hi] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return createHash("sha256")
+    .update(Buffer.from([1]))
+    .update(lo)
+    .update(hi)
+    .digest();
+};
+
+function merkleLevels(bidders: PublicKey[]): Buffer[][] {
+  const levels = [bidders.map(merkleLeaf)];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(
+        i + 1 < level.length ? merkleParent(level[i], level[i + 1]) : level[i],
+      );
+    }
+    levels.push(next);
+  }
+  return levels;
+}
+
+/**
+ * Allowlist root over `bidders`, matching bidx::merkle::build_root.
+ */
+export function merkleRoot(bidders: PublicKey[]): number[] {
+  const levels = merkleLevels(bidders);
+  return Array.from(levels[levels.length - 1][0]);
+}
+
+/**
+ * Allowlist proof for `bidder`, matching bidx::merkle::build_proof.
+ */
+export function merkleProof(
+  bidders: PublicKey[],
+  bidder: PublicKey,
+): number[][] {
+  let index = bidders.findIndex((key) => key.equals(bidder));
+  const proof: number[][] = [];
+  for (const level of merkleLevels(bidders)) {
+    if (level.length === 1) break;
+    const sibling = level[index ^ 1];
+    if (sibling) proof.push(Array.from(sibling));
+    index = Math.floor(index / 2);
+  }
+  return proof;
+}
+
+export interface CommitContext extends BidContext {
+  salt: Buffer;
+  deposit: BN;
+}
+
+/**
+ * Commits a sealed bid (hash + deposit) and returns bid-related accounts.
+ */
+export async function setupCommit(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  auctionPDA: PublicKey,
+  bidAmount: BN,
+  deposit: BN = bidAmount,
+): Promise<CommitContext> {
+  const bidder = Keypair.generate();
+  await airdrop(connection, bidder.publicKey);
+
+  const bidderTokenAccount = await createFundedTokenAccount(
+    connection,
+    platform.admin,
+    platform.usdcMint,
+    bidder.publicKey,
+    deposit.toNumber() * 2,
+  );
+
+  const [bid] = getBidPDA(bidder.publicKey, auctionPDA, program.programId);
+  const escrowVault = getAssociatedTokenAddressSync(
+    platform.usdcMint,
+    bid,
+    true,
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  );
+
+  const salt = Keypair.generate().publicKey.toBuffer();
+
   await program.methods
-    .placeBid(bidAmount)
+    .commitBid(
+      sealedCommitment(bidAmount, salt, bidder.publicKey),
+      deposit,
+      [],
+    )
     .accounts({
       bidder: bidder.publicKey,
       bid,
       auction: auctionPDA,
+      platformConfig: platform.platformConfig,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      bond: null,
       bidderTokenAccount,
       escrowVault,
       tokenMint: platform.usdcMint,
@@ -530,6 +881,8 @@ export async function setupBid(
     escrowVault,
     bid,
     bidAmount,
+    salt,
+    deposit,
   };
 }
 
@@ -0,0 +1,302 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import { randomBytes } from "crypto";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  endAuction,
+  setupBid,
+  setupDigitalNftAuction,
+  waitForUnixTimestamp,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+const RESERVE = new BN(5_000_000);
+
+export function runHiddenReserveTests(getCtx: () => Ctx) {
+  describe("hidden reserve", () => {
+    function revealReserve(
+      auctionCtx: AuctionContext,
+      reserve: BN,
+      salt: Buffer,
+    ) {
+      const { program } = getCtx();
+      return program.methods
+        .revealReserve(new BN(0), reserve, Array.from(salt))
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+        })
+        .rpc();
+    }
+
+    it("reserve stays hidden until a matching reveal after the auction ends", async () => {
+      const { program, connection, platform } = getCtx();
+      const salt = randomBytes(32);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { hiddenReserve: { reserve: RESERVE, salt, revealWindowSecs: 60 } },
+      );
+
+      let auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.reservedPrice.toNumber()).to.equal(0);
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+      await assertAnchorError(
+        revealReserve(auctionCtx, RESERVE, salt),
+        "AuctionNotEnded",
+      );
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      await assertAnchorError(
+        revealReserve(auctionCtx, RESERVE, randomBytes(32)),
+        "ReserveCommitmentMismatch",
+      );
+
+      await revealReserve(auctionCtx, RESERVE, salt);
+      auctionData = await program.account.auction.fetch(auctionCtx.auction);
+      expect(auctionData.reservedPrice.toNumber()).to.equal(
+        RESERVE.toNumber(),
+      );
+      expect(auctionData.hiddenReserve).to.be.null;
+    });
+
+    it("refunds every bidder in full when the reserve is never revealed", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          hiddenReserve: {
+            reserve: RESERVE,
+            salt: randomBytes(32),
+            revealWindowSecs: 2,
+          },
+        },
+      );
+
+      const winner = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      await waitForUnixTimestamp(
+        auctionData.hiddenReserve.revealDeadline.toNumber() + 1,
+      );
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: winner.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: winner.bid,
+          escrowVault: winner.escrowVault,
+          bidderTokenAccount: winner.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([winner.bidder])
+        .rpc();
+
+      // setupBid funds 10x the bid, all of it back in the bidder's account
+      const refunded = await getAccount(
+        connection,
+        winner.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(60_000_000);
+
+      const failed = await program.account.auction.fetch(auctionCtx.auction);
+      expect(failed.auctionStatus).to.deep.equal({ failed: {} });
+    });
+
+    it("lets the seller reclaim the item once the reveal deadline passes", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          hiddenReserve: {
+            reserve: RESERVE,
+            salt: randomBytes(32),
+            revealWindowSecs: 2,
+          },
+        },
+      );
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      await waitForUnixTimestamp(
+        auctionData.hiddenReserve.revealDeadline.toNumber() + 1,
+      );
+
+      await program.methods
+        .cancelAuction(new BN(0), null)
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          sellerNftAccount: auctionCtx.sellerNftAccount,
+          sellerTokenAccount: null,
+          treasury: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([auctionCtx.seller])
+        .rpc();
+
+      const returned = await getAccount(
+        connection,
+        auctionCtx.sellerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(returned.amount)).to.equal(1);
+    });
+
+    it("fails the auction when the revealed reserve is above the highest bid", async () => {
+      const { program, connection, platform } = getCtx();
+      const salt = randomBytes(32);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { hiddenReserve: { reserve: RESERVE, salt, revealWindowSecs: 60 } },
+      );
+
+      const leader = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(3_000_000),
+      );
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      await revealReserve(auctionCtx, RESERVE, salt);
+
+      const failed = await program.account.auction.fetch(auctionCtx.auction);
+      expect(failed.auctionStatus).to.deep.equal({ failed: {} });
+
+      // the leader is refunded in full
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: leader.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: leader.bid,
+          escrowVault: leader.escrowVault,
+          bidderTokenAccount: leader.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([leader.bidder])
+        .rpc();
+      const refunded = await getAccount(
+        connection,
+        leader.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(30_000_000);
+
+      // and the seller takes the item back without a fee
+      await program.methods
+        .cancelAuction(new BN(0), null)
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          sellerNftAccount: auctionCtx.sellerNftAccount,
+          sellerTokenAccount: null,
+          treasury: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([auctionCtx.seller])
+        .rpc();
+      const item = await getAccount(
+        connection,
+        auctionCtx.sellerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(item.amount)).to.equal(1);
+    });
+  });
+}
@@ -0,0 +1,157 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  createFundedTokenAccount,
+  endAuction,
+  getLotManifestPDA,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runLotTests(getCtx: () => Ctx) {
+  describe("lot auctions", () => {
+    it("winner receives every item in the lot", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { lotSize: 2 },
+      );
+      const [lotManifest] = getLotManifestPDA(
+        auctionCtx.auction,
+        program.programId,
+      );
+
+      const manifest = await program.account.lotManifest.fetch(lotManifest);
+      expect(manifest.items.map((i) => i.vault.toBase58())).to.deep.equal(
+        auctionCtx.lotItems.map((i) => i.vault.toBase58()),
+      );
+
+      const winner = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      // winner needs an account for the primary item and each lot item
+      const mints = [
+        auctionCtx.nftMint,
+        ...auctionCtx.lotItems.map((i) => i.mint),
+      ];
+      const destinations = mints.map((mint) =>
+        getAssociatedTokenAddressSync(
+          mint,
+          winner.bidder.publicKey,
+          false,
+          TOKEN_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        ),
+      );
+      const [winnerNftAccount, ...lotDestinations] = destinations;
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ...mints.map((mint, i) =>
+            createAssociatedTokenAccountInstruction(
+              winner.bidder.publicKey,
+              destinations[i],
+              winner.bidder.publicKey,
+              mint,
+              TOKEN_PROGRAM_ID,
+              ASSOCIATED_TOKEN_PROGRAM_ID,
+            ),
+          ),
+        ),
+        [winner.bidder],
+      );
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      await program.methods
+        .settleAuction(new BN(0), null)
+        .accounts({
+          winner: winner.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          authenticator: Keypair.generate().publicKey,
+          auction: auctionCtx.auction,
+          bid: winner.bid,
+          authentication: null,
+          unitBook: null,
+          lotManifest,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          escrowVault: winner.escrowVault,
+          sellerTokenAccount,
+          treasury: platform.treasuryUsdc,
+          authenticatorTokenAccount: null,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          winnerNftAccount,
+          winnerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          auctionCtx.lotItems.flatMap((item, i) => [
+            { pubkey: item.mint, isSigner: false, isWritable: false },
+            { pubkey: item.vault, isSigner: false, isWritable: true },
+            { pubkey: lotDestinations[i], isSigner: false, isWritable: true },
+          ]),
+        )
+        .signers([winner.bidder])
+        .rpc();
+
+      for (const account of destinations) {
+        const item = await getAccount(
+          connection,
+          account,
+          undefined,
+          TOKEN_PROGRAM_ID,
+        );
+        expect(Number(item.amount)).to.equal(1);
+      }
+    });
+  });
+}
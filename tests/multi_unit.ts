@@ -0,0 +1,345 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  fund,
+  assertAnchorError,
+  createFundedTokenAccount,
+  endAuction,
+  getBidPDA,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+interface UnitBidContext {
+  bidder: Keypair;
+  bidderTokenAccount: PublicKey;
+  escrowVault: PublicKey;
+  bid: PublicKey;
+}
+
+export function getUnitBookPDA(
+  auction: PublicKey,
+  programId: PublicKey,
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("unit_book"), auction.toBuffer()],
+    programId,
+  );
+}
+
+export function runMultiUnitTests(getCtx: () => Ctx) {
+  describe("multi-unit auctions", () => {
+    let auctionCtx: AuctionContext;
+    let unitBook: PublicKey;
+
+    async function bidUnits(
+      unitPrice: number,
+      quantity: number,
+      auction: PublicKey = auctionCtx.auction,
+      book: PublicKey = unitBook,
+    ): Promise<UnitBidContext> {
+      const { program, connection, platform } = getCtx();
+      const bidder = Keypair.generate();
+      await fund(connection, bidder.publicKey);
+
+      const bidderTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        bidder.publicKey,
+        unitPrice * quantity,
+      );
+      const [bid] = getBidPDA(bidder.publicKey, auction, program.programId);
+      const escrowVault = getAssociatedTokenAddressSync(
+        platform.usdcMint,
+        bid,
+        true,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+      await program.methods
+        .bidUnits(new BN(unitPrice), new BN(quantity), [])
+        .accounts({
+          bidder: bidder.publicKey,
+          bid,
+          auction,
+          platformConfig: platform.platformConfig,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          bond: null,
+          unitBook: book,
+          bidderTokenAccount,
+          escrowVault,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([bidder])
+        .rpc();
+
+      return { bidder, bidderTokenAccount, escrowVault, bid };
+    }
+
+    before(async () => {
+      const { program, connection, platform } = getCtx();
+      auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { multiUnit: { quantity: new BN(3), pricing: { uniform: {} } } },
+      );
+      [unitBook] = getUnitBookPDA(auctionCtx.auction, program.programId);
+    });
+
+    it("rejects single-unit bids", async () => {
+      const { program, connection, platform } = getCtx();
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(6_000_000),
+        ),
+        "MultiUnitOnly",
+      );
+    });
+
+    it("top bids win at the uniform clearing price; outbid bidders withdraw", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const big = await bidUnits(7_000_000, 2);
+      const outbid = await bidUnits(6_000_000, 1);
+      await bidUnits(6_500_000, 1); // pushes `outbid` out of the top 3 units
+
+      const book = await program.account.unitBook.fetch(unitBook);
+      expect(book.entries.map((e) => e.bidder.toBase58())).to.not.include(
+        outbid.bidder.publicKey.toBase58(),
+      );
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: outbid.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: outbid.bid,
+          escrowVault: outbid.escrowVault,
+          bidderTokenAccount: outbid.bidderTokenAccount,
+          unitBook,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([outbid.bidder])
+        .rpc();
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      const winnerNftAccount = getAssociatedTokenAddressSync(
+        auctionCtx.nftMint,
+        big.bidder.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            big.bidder.publicKey,
+            winnerNftAccount,
+            big.bidder.publicKey,
+            auctionCtx.nftMint,
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+        ),
+        [big.bidder],
+      );
+
+      await program.methods
+        .settleAuction(new BN(0), null)
+        .accounts({
+          winner: big.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          authenticator: Keypair.generate().publicKey,
+          auction: auctionCtx.auction,
+          bid: big.bid,
+          authentication: null,
+          unitBook,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          escrowVault: big.escrowVault,
+          sellerTokenAccount,
+          treasury: platform.treasuryUsdc,
+          authenticatorTokenAccount: null,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          winnerNftAccount,
+          winnerTokenAccount: big.bidderTokenAccount,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([big.bidder])
+        .rpc();
+
+      const units = await getAccount(
+        connection,
+        winnerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(units.amount)).to.equal(2);
+
+      // 2 units at the 6.5 USDC clearing price, 1 USDC refunded
+      const refunded = await getAccount(
+        connection,
+        big.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(1_000_000);
+
+      // one winner still to settle
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ ended: {} });
+      expect(auctionData.unitsSettled.toNumber()).to.equal(2);
+    });
+
+    it("winners under the reserve are refunded and the seller reclaims unsold units", async () => {
+      const { program, connection, platform } = getCtx();
+      const payAsBid = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { multiUnit: { quantity: new BN(3), pricing: { payAsBid: {} } } },
+      );
+      const [payAsBidBook] = getUnitBookPDA(
+        payAsBid.auction,
+        program.programId,
+      );
+
+      // one unit clears the 5 USDC reserve, one wins below it, one goes unsold
+      await bidUnits(7_000_000, 1, payAsBid.auction, payAsBidBook);
+      const underReserve = await bidUnits(
+        4_000_000,
+        1,
+        payAsBid.auction,
+        payAsBidBook,
+      );
+      await endAuction(program, payAsBid.auction, payAsBid.seller.publicKey, 0);
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: underReserve.bidder.publicKey,
+          seller: payAsBid.seller.publicKey,
+          auction: payAsBid.auction,
+          bid: underReserve.bid,
+          escrowVault: underReserve.escrowVault,
+          bidderTokenAccount: underReserve.bidderTokenAccount,
+          unitBook: payAsBidBook,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([underReserve.bidder])
+        .rpc();
+      const refunded = await getAccount(
+        connection,
+        underReserve.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(4_000_000);
+
+      const reclaim = () =>
+        program.methods
+          .reclaimUnsoldUnits(new BN(0))
+          .accounts({
+            seller: payAsBid.seller.publicKey,
+            auction: payAsBid.auction,
+            unitBook: payAsBidBook,
+            nftMint: payAsBid.nftMint,
+            itemVault: payAsBid.itemVault,
+            sellerNftAccount: payAsBid.sellerNftAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([payAsBid.seller])
+          .rpc();
+
+      // the unit owed to the 7 USDC winner stays in the vault
+      await reclaim();
+      const returned = await getAccount(
+        connection,
+        payAsBid.sellerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(returned.amount)).to.equal(2);
+      const vault = await getAccount(
+        connection,
+        payAsBid.itemVault,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(vault.amount)).to.equal(1);
+
+      await assertAnchorError(reclaim(), "NoUnsoldUnits");
+    });
+  });
+}
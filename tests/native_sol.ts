@@ -0,0 +1,273 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getAccount,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  airdrop,
+  assertAnchorError,
+  getBidPDA,
+  setupDigitalNftAuction,
+  endAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+// accepted_token sentinel for native SOL auctions
+const NATIVE_SOL = SystemProgram.programId;
+
+export async function placeNativeBid(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  auction: PublicKey,
+  amount: BN,
+  priceUpdate: PublicKey | null = null, // prices a USD starting bid
+  proxy = false,
+): Promise<{ bidder: Keypair; bid: PublicKey }> {
+  const bidder = Keypair.generate();
+  await airdrop(connection, bidder.publicKey);
+  const [bid] = getBidPDA(bidder.publicKey, auction, program.programId);
+
+  await (proxy
+    ? program.methods.placeProxyBid(amount, [])
+    : program.methods.placeBid(amount, [])
+  )
+    .accounts({
+      bidder: bidder.publicKey,
+      bid,
+      auction,
+      platformConfig: platform.platformConfig,
+      priceUpdate,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      bond: null,
+      bidderTokenAccount: null,
+      escrowVault: null,
+      tokenMint: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([bidder])
+    .rpc();
+
+  return { bidder, bid };
+}
+
+export function runNativeSolTests(getCtx: () => Ctx) {
+  describe("native_sol", () => {
+    it("escrows bids as lamports on the bid PDA", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { acceptedToken: NATIVE_SOL },
+      );
+
+      const amount = new BN(6_000_000);
+      const { bidder, bid } = await placeNativeBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        amount,
+      );
+
+      const bidData = await program.account.bid.fetch(bid);
+      expect(bidData.amount.toNumber()).to.equal(amount.toNumber());
+      expect(bidData.tokenMint.toBase58()).to.equal(NATIVE_SOL.toBase58());
+      expect(bidData.bidder.toBase58()).to.equal(bidder.publicKey.toBase58());
+
+      const rent = await connection.getMinimumBalanceForRentExemption(
+        (await connection.getAccountInfo(bid))!.data.length,
+      );
+      expect(await connection.getBalance(bid)).to.equal(
+        rent + amount.toNumber(),
+      );
+    });
+
+    it("refunds outbid lamports and settles proceeds to the seller wallet", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { acceptedToken: NATIVE_SOL },
+      );
+
+      const loser = await placeNativeBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+      const winner = await placeNativeBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(8_000_000),
+      );
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      // outbid bidder reclaims the escrow and the bid PDA rent
+      const loserEscrow = await connection.getBalance(loser.bid);
+      const loserBefore = await connection.getBalance(loser.bidder.publicKey);
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: loser.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: loser.bid,
+          escrowVault: null,
+          bidderTokenAccount: null,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([loser.bidder])
+        .rpc();
+
+      expect(await connection.getAccountInfo(loser.bid)).to.be.null;
+      const loserAfter = await connection.getBalance(loser.bidder.publicKey);
+      // the bidder pays the transaction fee, so allow a little slack
+      expect(loserAfter - loserBefore).to.be.gte(loserEscrow - 10_000);
+
+      const winnerNftAccount = getAssociatedTokenAddressSync(
+        auctionCtx.nftMint,
+        winner.bidder.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            winner.bidder.publicKey,
+            winnerNftAccount,
+            winner.bidder.publicKey,
+            auctionCtx.nftMint,
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+        ),
+        [winner.bidder],
+      );
+
+      const sellerBefore = await connection.getBalance(
+        auctionCtx.seller.publicKey,
+      );
+      const treasuryBefore = await getAccount(
+        connection,
+        platform.treasurySol,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+
+      await program.methods
+        .settleAuction(new BN(0), null)
+        .accounts({
+          winner: winner.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          authenticator: Keypair.generate().publicKey,
+          auction: auctionCtx.auction,
+          bid: winner.bid,
+          authentication: null,
+          unitBook: null,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          escrowVault: null,
+          sellerTokenAccount: null,
+          treasury: platform.treasurySol,
+          authenticatorTokenAccount: null,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          winnerNftAccount,
+          winnerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([winner.bidder])
+        .rpc();
+
+      const winnerNft = await getAccount(
+        connection,
+        winnerNftAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(winnerNft.amount)).to.equal(1);
+
+      // platform fee: 8_000_000 * 250 / 10_000 = 200_000, wrapped as wSOL
+      const fee = Math.floor((8_000_000 * 250) / 10_000);
+      const treasuryAfter = await getAccount(
+        connection,
+        platform.treasurySol,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(treasuryAfter.amount - treasuryBefore.amount)).to.equal(
+        fee,
+      );
+
+      const sellerAfter = await connection.getBalance(
+        auctionCtx.seller.publicKey,
+      );
+      expect(sellerAfter - sellerBefore).to.equal(8_000_000 - fee);
+    });
+
+    it("rejects native SOL with buy-now", async () => {
+      const { program, connection, platform } = getCtx();
+
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          acceptedToken: NATIVE_SOL,
+          buyNowPrice: new BN(20_000_000),
+        }),
+        "NativeSolUnsupported",
+      );
+    });
+  });
+}
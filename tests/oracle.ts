@@ -0,0 +1,370 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  setupDigitalNftAuction,
+  endAuction,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+import { placeNativeBid } from "./native_sol";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+// Mock Pyth PriceUpdateV2 accounts loaded by Anchor.toml from tests/fixtures:
+// SOL/USD at $150.00 published at 1_700_000_000, confidence 0.01% and 5%.
+const MOCK_PRICE_UPDATE = new PublicKey(
+  "8b1CrhgbAHWfVVRFYPC4ydmE3UxeoYo8DxYcbecCqgXJ",
+);
+const MOCK_WIDE_PRICE_UPDATE = new PublicKey(
+  "36RPbg4uJujaj79fzD2AYCZCtRkyjNLGPiqHBUDG9pb5",
+);
+const SOL_USD_FEED_ID = Array.from(
+  Buffer.from(
+    "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+    "hex",
+  ),
+);
+
+// the fixtures are years old, so only the staleness test uses a tight bound
+const TEN_YEARS = new BN(10 * 365 * 24 * 60 * 60);
+
+// setupDigitalNftAuction prices at a $1 start and $5 reserve (6 decimals)
+function usdAuction(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  maxStaleness: BN = TEN_YEARS,
+): Promise<AuctionContext> {
+  return setupDigitalNftAuction(program, connection, platform, 0, {
+    acceptedToken: SystemProgram.programId,
+    usdPricing: {
+      feedId: SOL_USD_FEED_ID,
+      maxStaleness,
+      maxConfBps: 100,
+    },
+  });
+}
+
+async function bidAndEnd(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  auctionCtx: AuctionContext,
+  lamports: number,
+  proxy = false,
+) {
+  const winner = await placeNativeBid(
+    program,
+    connection,
+    platform,
+    auctionCtx.auction,
+    new BN(lamports),
+    MOCK_PRICE_UPDATE,
+    proxy,
+  );
+  await endAuction(
+    program,
+    auctionCtx.auction,
+    auctionCtx.seller.publicKey,
+    0,
+  );
+
+  const winnerNftAccount = getAssociatedTokenAddressSync(
+    auctionCtx.nftMint,
+    winner.bidder.publicKey,
+    false,
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  );
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        winner.bidder.publicKey,
+        winnerNftAccount,
+        winner.bidder.publicKey,
+        auctionCtx.nftMint,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      ),
+    ),
+    [winner.bidder],
+  );
+
+  return { ...winner, winnerNftAccount };
+}
+
+function settle(
+  program: Program<Bidx>,
+  platform: PlatformContext,
+  auctionCtx: AuctionContext,
+  winner: { bidder: Keypair; bid: PublicKey; winnerNftAccount: PublicKey },
+  priceUpdate: PublicKey | null,
+) {
+  return program.methods
+    .settleAuction(new BN(0), null)
+    .accounts({
+      winner: winner.bidder.publicKey,
+      seller: auctionCtx.seller.publicKey,
+      authenticator: Keypair.generate().publicKey,
+      auction: auctionCtx.auction,
+      bid: winner.bid,
+      authentication: null,
+      unitBook: null,
+      lotManifest: null,
+      platformConfig: platform.platformConfig,
+      priceUpdate,
+      escrowVault: null,
+      sellerTokenAccount: null,
+      treasury: platform.treasurySol,
+      authenticatorTokenAccount: null,
+      nftMint: auctionCtx.nftMint,
+      itemVault: auctionCtx.itemVault,
+      winnerNftAccount: winner.winnerNftAccount,
+      winnerTokenAccount: null,
+      bond: null,
+      bondVault: null,
+      tokenMint: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([winner.bidder])
+    .rpc();
+}
+
+export function runOracleTests(getCtx: () => Ctx) {
+  describe("oracle", () => {
+    before(async function () {
+      const { connection } = getCtx();
+      // the mock price accounts only exist on a local validator
+      if (!(await connection.getAccountInfo(MOCK_PRICE_UPDATE))) {
+        this.skip();
+      }
+    });
+
+    it("settles a USD reserve met at the oracle price", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await usdAuction(program, connection, platform);
+      // 0.04 SOL at $150 = $6.00, over the $5 reserve
+      const winner = await bidAndEnd(
+        program,
+        connection,
+        platform,
+        auctionCtx,
+        40_000_000,
+      );
+
+      await assertAnchorError(
+        settle(program, platform, auctionCtx, winner, null),
+        "PriceUpdateRequired",
+      );
+      await assertAnchorError(
+        settle(program, platform, auctionCtx, winner, MOCK_WIDE_PRICE_UPDATE),
+        "PriceConfidenceTooWide",
+      );
+
+      await settle(program, platform, auctionCtx, winner, MOCK_PRICE_UPDATE);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ settled: {} });
+    });
+
+    it("prices the opening bid against the USD starting bid", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await usdAuction(program, connection, platform);
+      const openingBid = (lamports: number, priceUpdate: PublicKey | null) =>
+        placeNativeBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(lamports),
+          priceUpdate,
+        );
+
+      await assertAnchorError(
+        openingBid(10_000_000, null),
+        "PriceUpdateRequired",
+      );
+      // 0.006 SOL at $150 = $0.90, under the $1 start
+      await assertAnchorError(
+        openingBid(6_000_000, MOCK_PRICE_UPDATE),
+        "BelowMinimumBid",
+      );
+      // 0.01 SOL at $150 = $1.50
+      await openingBid(10_000_000, MOCK_PRICE_UPDATE);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(10_000_000);
+    });
+
+    it("fails the auction when the winning bid is worth less than the USD reserve", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await usdAuction(program, connection, platform);
+      // 0.02 SOL at $150 = $3.00
+      const winner = await bidAndEnd(
+        program,
+        connection,
+        platform,
+        auctionCtx,
+        20_000_000,
+      );
+
+      await settle(program, platform, auctionCtx, winner, MOCK_PRICE_UPDATE);
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ failed: {} });
+
+      // the item stays in the vault and the winner is refunded
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: winner.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: winner.bid,
+          escrowVault: null,
+          bidderTokenAccount: null,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([winner.bidder])
+        .rpc();
+      expect(await connection.getAccountInfo(winner.bid)).to.be.null;
+    });
+
+    it("settles a proxy maximum covering the USD reserve at the reserve", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await usdAuction(program, connection, platform);
+      // a 0.04 SOL ($6.00) maximum stands at the opening bid, under the $5 reserve
+      const winner = await bidAndEnd(
+        program,
+        connection,
+        platform,
+        auctionCtx,
+        40_000_000,
+        true,
+      );
+      const before = await connection.getBalance(winner.bidder.publicKey);
+
+      await settle(program, platform, auctionCtx, winner, MOCK_PRICE_UPDATE);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ settled: {} });
+      // $5 at $150 is 0.0333... SOL, rounded up; the rest of the maximum is refunded
+      const refunded =
+        (await connection.getBalance(winner.bidder.publicKey)) - before;
+      expect(refunded).to.be.greaterThan(40_000_000 - 33_333_334 - 10_000);
+    });
+
+    it("lets anyone fail an ended auction whose leader is worth less than the USD reserve", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const failAuction = (auctionCtx: AuctionContext) =>
+        program.methods
+          .failAuction(new BN(0))
+          .accounts({
+            seller: auctionCtx.seller.publicKey,
+            auction: auctionCtx.auction,
+            priceUpdate: MOCK_PRICE_UPDATE,
+            tokenMint: null,
+          })
+          .rpc();
+
+      // 0.04 SOL at $150 = $6.00 meets the $5 reserve
+      const met = await usdAuction(program, connection, platform);
+      await bidAndEnd(program, connection, platform, met, 40_000_000);
+      await assertAnchorError(failAuction(met), "ReserveMet");
+
+      // 0.02 SOL at $150 = $3.00
+      const unmet = await usdAuction(program, connection, platform);
+      await bidAndEnd(program, connection, platform, unmet, 20_000_000);
+      await failAuction(unmet);
+
+      const auctionData = await program.account.auction.fetch(unmet.auction);
+      expect(auctionData.auctionStatus).to.deep.equal({ failed: {} });
+    });
+
+    it("rejects a stale price", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await usdAuction(
+        program,
+        connection,
+        platform,
+        new BN(60),
+      );
+
+      await assertAnchorError(
+        placeNativeBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(40_000_000),
+          MOCK_PRICE_UPDATE,
+        ),
+        "StalePrice",
+      );
+    });
+
+    it("rejects USD pricing on a Dutch auction", async () => {
+      const { program, connection, platform } = getCtx();
+
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          auctionType: {
+            dutch: {
+              startPrice: new BN(20_000_000),
+              floorPrice: new BN(6_000_000),
+              decay: { linear: {} },
+            },
+          },
+          usdPricing: {
+            feedId: SOL_USD_FEED_ID,
+            maxStaleness: TEN_YEARS,
+            maxConfBps: 100,
+          },
+        }),
+        "InvalidUsdPricing",
+      );
+    });
+  });
+}
@@ -0,0 +1,127 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  setupBid,
+  setupDigitalNftAuction,
+  setupPlatform,
+  PlatformContext,
+  AuctionContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runPauseAuctionTests(getCtx: () => Ctx) {
+  describe("pause_auction / resume_auction", () => {
+    function accounts(
+      auctionCtx: AuctionContext,
+      authority: Keypair,
+      platformConfig: PublicKey = getCtx().platform.platformConfig,
+    ) {
+      return {
+        authority: authority.publicKey,
+        seller: auctionCtx.seller.publicKey,
+        auction: auctionCtx.auction,
+        platformConfig,
+      };
+    }
+
+    it("paused auctions reject bids and resume with their end date pushed back", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+
+      await program.methods
+        .pauseAuction(new BN(0))
+        .accounts(accounts(auctionCtx, auctionCtx.seller))
+        .signers([auctionCtx.seller])
+        .rpc();
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(2_000_000),
+        ),
+        "AuctionPaused",
+      );
+
+      const paused = await program.account.auction.fetch(auctionCtx.auction);
+
+      // resumed by the platform admin
+      await program.methods
+        .resumeAuction(new BN(0))
+        .accounts(accounts(auctionCtx, platform.admin))
+        .signers([platform.admin])
+        .rpc();
+
+      const resumed = await program.account.auction.fetch(auctionCtx.auction);
+      expect(resumed.auctionStatus).to.deep.equal({ active: {} });
+      expect(resumed.endDate.toNumber()).to.be.gt(paused.endDate.toNumber());
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+    });
+
+    it("only the seller or platform admin can pause", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      const stranger = Keypair.generate();
+
+      await assertAnchorError(
+        program.methods
+          .pauseAuction(new BN(0))
+          .accounts(accounts(auctionCtx, stranger))
+          .signers([stranger])
+          .rpc(),
+        "NotSellerOrAdmin",
+      );
+    });
+
+    it("admins of other platform configs can't pause", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      const otherPlatform = await setupPlatform(program, connection);
+
+      await assertAnchorError(
+        program.methods
+          .pauseAuction(new BN(0))
+          .accounts(
+            accounts(
+              auctionCtx,
+              otherPlatform.admin,
+              otherPlatform.platformConfig,
+            ),
+          )
+          .signers([otherPlatform.admin])
+          .rpc(),
+        "WrongPlatformConfig",
+      );
+    });
+  });
+}
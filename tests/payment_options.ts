@@ -0,0 +1,352 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getAccount,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  airdrop,
+  assertAnchorError,
+  createFundedTokenAccount,
+  createTokenMint,
+  getBidPDA,
+  setupDigitalNftAuction,
+  endAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+// matches RATE_SCALE on-chain
+const RATE_SCALE = new BN(1_000_000_000);
+
+interface MintBid {
+  bidder: Keypair;
+  bid: PublicKey;
+  bidderTokenAccount: PublicKey;
+  escrowVault: PublicKey;
+}
+
+export async function placeBidIn(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  auction: PublicKey,
+  mint: PublicKey,
+  amount: BN,
+): Promise<MintBid> {
+  const bidder = Keypair.generate();
+  await airdrop(connection, bidder.publicKey);
+  const bidderTokenAccount = await createFundedTokenAccount(
+    connection,
+    platform.admin,
+    mint,
+    bidder.publicKey,
+    amount.toNumber() * 10,
+  );
+  const [bid] = getBidPDA(bidder.publicKey, auction, program.programId);
+  const escrowVault = getAssociatedTokenAddressSync(
+    mint,
+    bid,
+    true,
+    TOKEN_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  );
+
+  await program.methods
+    .placeBid(amount, [])
+    .accounts({
+      bidder: bidder.publicKey,
+      bid,
+      auction,
+      platformConfig: platform.platformConfig,
+      priceUpdate: null,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      bond: null,
+      bidderTokenAccount,
+      escrowVault,
+      tokenMint: mint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([bidder])
+    .rpc();
+
+  return { bidder, bid, bidderTokenAccount, escrowVault };
+}
+
+export function runPaymentOptionsTests(getCtx: () => Ctx) {
+  describe("payment_options", () => {
+    let usdt: PublicKey;
+    let treasuryUsdt: PublicKey;
+
+    before(async () => {
+      const { program, connection, platform } = getCtx();
+
+      usdt = await createTokenMint(connection, platform.admin, 6);
+      treasuryUsdt = getAssociatedTokenAddressSync(
+        usdt,
+        platform.platformConfig,
+        true,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+      await program.methods
+        .openTreasuryVault()
+        .accounts({
+          admin: platform.admin.publicKey,
+          platformConfig: platform.platformConfig,
+          treasuryVault: treasuryUsdt,
+          tokenMint: usdt,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([platform.admin])
+        .rpc();
+    });
+
+    it("compares bids across mints in base units and settles in the winner's mint", async () => {
+      const { program, connection, platform } = getCtx();
+
+      // 1 raw USDT unit is worth 2 base (USDC) units
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { paymentOptions: [{ mint: usdt, rate: RATE_SCALE.muln(2) }] },
+      );
+
+      const usdcBid = await placeBidIn(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        platform.usdcMint,
+        new BN(6_000_000),
+      );
+      // 3_500_000 USDT = 7_000_000 base units
+      const usdtBid = await placeBidIn(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        usdt,
+        new BN(3_500_000),
+      );
+
+      let auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(7_000_000);
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        usdtBid.bidder.publicKey.toBase58(),
+      );
+      const bidData = await program.account.bid.fetch(usdtBid.bid);
+      expect(bidData.tokenMint.toBase58()).to.equal(usdt.toBase58());
+      expect(bidData.amount.toNumber()).to.equal(3_500_000);
+
+      // 3_000_000 USDT is only 6_000_000 base units
+      await assertAnchorError(
+        placeBidIn(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          usdt,
+          new BN(3_000_000),
+        ),
+        "BelowMinimumBid",
+      );
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      const sellerUsdt = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        usdt,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      const winnerNftAccount = getAssociatedTokenAddressSync(
+        auctionCtx.nftMint,
+        usdtBid.bidder.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            usdtBid.bidder.publicKey,
+            winnerNftAccount,
+            usdtBid.bidder.publicKey,
+            auctionCtx.nftMint,
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+        ),
+        [usdtBid.bidder],
+      );
+
+      await program.methods
+        .settleAuction(new BN(0), null)
+        .accounts({
+          winner: usdtBid.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          authenticator: Keypair.generate().publicKey,
+          auction: auctionCtx.auction,
+          bid: usdtBid.bid,
+          authentication: null,
+          unitBook: null,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          escrowVault: usdtBid.escrowVault,
+          sellerTokenAccount: sellerUsdt,
+          treasury: treasuryUsdt,
+          authenticatorTokenAccount: null,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          winnerNftAccount,
+          winnerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: usdt,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([usdtBid.bidder])
+        .rpc();
+
+      // fees come out of the 3_500_000 USDT paid: 3_500_000 * 250 / 10_000
+      const fee = Math.floor((3_500_000 * 250) / 10_000);
+      const treasury = await getAccount(
+        connection,
+        treasuryUsdt,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(treasury.amount)).to.equal(fee);
+      const seller = await getAccount(
+        connection,
+        sellerUsdt,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(seller.amount)).to.equal(3_500_000 - fee);
+
+      auctionData = await program.account.auction.fetch(auctionCtx.auction);
+      expect(auctionData.auctionStatus).to.deep.equal({ settled: {} });
+
+      // the outbid USDC bidder is refunded in USDC
+      const before = await getAccount(
+        connection,
+        usdcBid.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: usdcBid.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: usdcBid.bid,
+          escrowVault: usdcBid.escrowVault,
+          bidderTokenAccount: usdcBid.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([usdcBid.bidder])
+        .rpc();
+      const after = await getAccount(
+        connection,
+        usdcBid.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(after.amount - before.amount)).to.equal(6_000_000);
+    });
+
+    it("rejects bids in a mint the auction doesn't accept", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+
+      await assertAnchorError(
+        placeBidIn(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          usdt,
+          new BN(6_000_000),
+        ),
+        "WrongToken",
+      );
+    });
+
+    it("rejects payment options outside English auctions", async () => {
+      const { program, connection, platform } = getCtx();
+
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          sealedBid: { revealWindowSecs: 10, penaltyBps: 500 },
+          paymentOptions: [{ mint: usdt, rate: RATE_SCALE }],
+        }),
+        "InvalidPaymentOptions",
+      );
+    });
+
+    it("rejects payment options alongside a participation bond", async () => {
+      const { program, connection, platform } = getCtx();
+
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          assetType: { physicalRwa: {} },
+          participationBond: new BN(1_000_000),
+          activate: false,
+          paymentOptions: [{ mint: usdt, rate: RATE_SCALE }],
+        }),
+        "InvalidPaymentOptions",
+      );
+    });
+  });
+}
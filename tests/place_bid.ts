@@ -15,7 +15,10 @@ import {
   createFundedTokenAccount,
   createTokenMint,
   getBidPDA,
+  setupBid,
   setupDigitalNftAuction,
+  setupPlatform,
+  waitForUnixTimestamp,
   PlatformContext,
   AuctionContext,
 } from "./helpers";
@@ -66,11 +69,16 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
       );
 
       await program.methods
-        .placeBid(bidAmount)
+        .placeBid(bidAmount, [])
         .accounts({
           bidder: bidder.publicKey,
           bid,
           auction: auctionCtx.auction,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          bond: null,
           bidderTokenAccount,
           escrowVault,
           tokenMint: platform.usdcMint,
@@ -137,6 +145,11 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
         bidder: bidder.publicKey,
         bid,
         auction: auctionCtx.auction,
+        platformConfig: platform.platformConfig,
+        priceUpdate: null,
+        gateTokenAccount: null,
+        gateMetadata: null,
+        bond: null,
         bidderTokenAccount,
         escrowVault,
         tokenMint: platform.usdcMint,
@@ -146,12 +159,12 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
       };
 
       await program.methods
-        .placeBid(firstBid)
+        .placeBid(firstBid, [])
         .accounts(accounts)
         .signers([bidder])
         .rpc();
       await program.methods
-        .placeBid(increase)
+        .placeBid(increase, [])
         .accounts(accounts)
         .signers([bidder])
         .rpc();
@@ -172,11 +185,13 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
     it("prevents bidding on `inactive` auction(s)", async () => {
       const { program, connection, platform } = getCtx();
 
-      // Fresh auction stays Pending
+      // Fresh auction stays Pending until activated
       const pendingAuction = await setupDigitalNftAuction(
         program,
         connection,
         platform,
+        0,
+        { activate: false },
       );
 
       const bidder = Keypair.generate();
@@ -206,11 +221,16 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .placeBid(bidAmount)
+          .placeBid(bidAmount, [])
           .accounts({
             bidder: bidder.publicKey,
             bid,
             auction: pendingAuction.auction,
+            platformConfig: platform.platformConfig,
+            priceUpdate: null,
+            gateTokenAccount: null,
+            gateMetadata: null,
+            bond: null,
             bidderTokenAccount,
             escrowVault,
             tokenMint: platform.usdcMint,
@@ -255,11 +275,16 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .placeBid(bidAmount)
+          .placeBid(bidAmount, [])
           .accounts({
             bidder: bidder.publicKey,
             bid,
             auction: auctionCtx.auction,
+            platformConfig: platform.platformConfig,
+            priceUpdate: null,
+            gateTokenAccount: null,
+            gateMetadata: null,
+            bond: null,
             bidderTokenAccount,
             escrowVault,
             tokenMint: wrongMint,
@@ -273,7 +298,62 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
       );
     });
 
-    it("prevents bid amount less than the current highest bid", async () => {
+    it("rejects a platform config other than the one the auction was created under", async () => {
+      const { program, connection, platform } = getCtx();
+      const bidder = Keypair.generate();
+      await fund(connection, bidder.publicKey);
+
+      // anyone can initialize a config of their own, e.g. one that is never paused
+      const otherPlatform = await setupPlatform(program, connection);
+      const bidAmount = new BN(2_000_000);
+
+      const bidderTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        bidder.publicKey,
+        bidAmount.toNumber(),
+      );
+
+      const [bid] = getBidPDA(
+        bidder.publicKey,
+        auctionCtx.auction,
+        program.programId,
+      );
+      const escrowVault = getAssociatedTokenAddressSync(
+        platform.usdcMint,
+        bid,
+        true,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+
+      await assertAnchorError(
+        program.methods
+          .placeBid(bidAmount, [])
+          .accounts({
+            bidder: bidder.publicKey,
+            bid,
+            auction: auctionCtx.auction,
+            platformConfig: otherPlatform.platformConfig,
+            priceUpdate: null,
+            gateTokenAccount: null,
+            gateMetadata: null,
+            bond: null,
+            bidderTokenAccount,
+            escrowVault,
+            tokenMint: platform.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bidder])
+          .rpc(),
+        "WrongPlatformConfig",
+      );
+    });
+
+    it("prevents bid amount below the minimum next bid", async () => {
       const { program, connection, platform } = getCtx();
       const bidder = Keypair.generate();
       await fund(connection, bidder.publicKey);
@@ -303,11 +383,16 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .placeBid(tooLow)
+          .placeBid(tooLow, [])
           .accounts({
             bidder: bidder.publicKey,
             bid,
             auction: auctionCtx.auction,
+            platformConfig: platform.platformConfig,
+            priceUpdate: null,
+            gateTokenAccount: null,
+            gateMetadata: null,
+            bond: null,
             bidderTokenAccount,
             escrowVault,
             tokenMint: platform.usdcMint,
@@ -317,7 +402,302 @@ export function runPlaceBidTests(getCtx: () => Ctx) {
           })
           .signers([bidder])
           .rpc(),
-        "BidTooLow",
+        "BelowMinimumBid",
+      );
+    });
+
+    it("first bid must reach starting_bid", async () => {
+      const { program, connection, platform } = getCtx();
+      const fresh = await setupDigitalNftAuction(program, connection, platform);
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          fresh.auction,
+          fresh.startingBid.subn(1),
+        ),
+        "BelowMinimumBid",
+      );
+    });
+
+    it("enforces the auction's minimum increment", async () => {
+      const { program, connection, platform } = getCtx();
+      const incremented = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { minIncrement: { absolute: { amount: new BN(500_000) } } },
+      );
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        incremented.auction,
+        new BN(2_000_000),
+      );
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          incremented.auction,
+          new BN(2_400_000),
+        ),
+        "BelowMinimumBid",
+      );
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        incremented.auction,
+        new BN(2_500_000),
+      );
+    });
+
+    it("rejects bids once end_date has passed", async () => {
+      const { program, connection, platform } = getCtx();
+      const closed = await setupDigitalNftAuction(program, connection, platform);
+      await waitForUnixTimestamp(closed.endDate.toNumber());
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          closed.auction,
+          new BN(2_000_000),
+        ),
+        "BiddingClosed",
+      );
+    });
+  });
+
+  describe("place_bid (soft close)", () => {
+    it("late bid inside the window pushes end_date back", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          softClose: {
+            window: new BN(60),
+            extension: new BN(120),
+            maxExtensions: null,
+          },
+        },
+      );
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.endDate.toNumber()).to.be.gt(
+        auctionCtx.endDate.toNumber(),
+      );
+      expect(auctionData.extensionCount).to.equal(1);
+    });
+
+    it("stops extending once max_extensions is reached", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          softClose: {
+            window: new BN(600),
+            extension: new BN(300),
+            maxExtensions: 1,
+          },
+        },
+      );
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+      );
+      const extended = await program.account.auction.fetch(auctionCtx.auction);
+
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(3_000_000),
+      );
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.extensionCount).to.equal(1);
+      expect(auctionData.endDate.toNumber()).to.equal(
+        extended.endDate.toNumber(),
+      );
+    });
+  });
+
+  describe("place_bid (dutch)", () => {
+    const dutch = {
+      dutch: {
+        startPrice: new BN(8_000_000),
+        floorPrice: new BN(5_000_000),
+        decay: { linear: {} },
+      },
+    };
+
+    it("first bidder at the current price wins and ends the auction", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { auctionType: dutch },
+      );
+
+      const winner = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(8_000_000),
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ ended: {} });
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        winner.bidder.publicKey.toBase58(),
+      );
+      expect(auctionData.highestBid.toNumber()).to.be.gte(5_000_000);
+      expect(auctionData.highestBid.toNumber()).to.be.lte(8_000_000);
+    });
+
+    it("rejects an amount below the current price", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { auctionType: dutch },
+      );
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(1_000_000),
+        ),
+        "BelowDutchPrice",
+      );
+    });
+  });
+
+  describe("place_bid (proxy)", () => {
+    it("proxy maximum defends the lead one increment at a time", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+      );
+      const fetchAuction = () =>
+        program.account.auction.fetch(auctionCtx.auction);
+
+      // 8 USDC maximum covers the 5 USDC reserve, so it opens at the reserve
+      const proxy = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(8_000_000),
+        true,
+      );
+      let auctionData = await fetchAuction();
+      expect(auctionData.highestBid.toNumber()).to.equal(5_000_000);
+      expect(auctionData.leaderMax.toNumber()).to.equal(8_000_000);
+
+      // a plain 6 USDC bid is answered one increment higher
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+      auctionData = await fetchAuction();
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        proxy.bidder.publicKey.toBase58(),
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(6_000_001);
+
+      // a higher maximum takes the lead at the old maximum plus one increment
+      const rival = await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(10_000_000),
+        true,
+      );
+      auctionData = await fetchAuction();
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        rival.bidder.publicKey.toBase58(),
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(8_000_001);
+      expect(auctionData.leaderMax.toNumber()).to.equal(10_000_000);
+    });
+
+    it("rejects proxy bids outside English auctions", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          auctionType: {
+            dutch: {
+              startPrice: new BN(8_000_000),
+              floorPrice: new BN(5_000_000),
+              decay: { linear: {} },
+            },
+          },
+        },
+      );
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(8_000_000),
+          true,
+        ),
+        "ProxyBidUnavailable",
       );
     });
   });
@@ -0,0 +1,363 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  ComputeBudgetProgram,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+  getAccount,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  airdrop,
+  assertAnchorError,
+  auctionTerms,
+  createFundedTokenAccount,
+  getAuctionPDA,
+  getAuthenticationPDA,
+  getSellerStatePDA,
+  setupBid,
+  endAuction,
+  waitForUnixTimestamp,
+  PlatformContext,
+  TEST_AUCTION_START_DELAY_SECS,
+  TEST_AUCTION_DURATION_SECS,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+// cloned from mainnet into the local validator by Anchor.toml
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+);
+
+const computeBudget = ComputeBudgetProgram.setComputeUnitLimit({
+  units: 400_000,
+});
+
+function metadataPDA(mint: PublicKey, ...suffix: Buffer[]): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      ...suffix,
+    ],
+    TOKEN_METADATA_PROGRAM_ID,
+  )[0];
+}
+
+const editionPDA = (mint: PublicKey) =>
+  metadataPDA(mint, Buffer.from("edition"));
+
+const tokenRecordPDA = (mint: PublicKey, token: PublicKey) =>
+  metadataPDA(mint, Buffer.from("token_record"), token.toBuffer());
+
+const meta = (
+  pubkey: PublicKey,
+  isWritable: boolean,
+  isSigner = false,
+): AccountMeta => ({ pubkey, isSigner, isWritable });
+
+function borshString(value: string): Buffer {
+  const bytes = Buffer.from(value);
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+}
+
+/**
+ * Creates a programmable NFT without a rule set and mints it to `owner`'s
+ * ATA, through Token Metadata's CreateV1 and MintV1.
+ */
+async function mintProgrammableNft(
+  connection: anchor.web3.Connection,
+  payer: Keypair,
+  owner: PublicKey,
+): Promise<{ mint: PublicKey; ownerAccount: PublicKey }> {
+  const mint = Keypair.generate();
+  const metadata = metadataPDA(mint.publicKey);
+  const edition = editionPDA(mint.publicKey);
+  const ownerAccount = getAssociatedTokenAddressSync(mint.publicKey, owner);
+
+  const sellerFeeBps = Buffer.alloc(2);
+  sellerFeeBps.writeUInt16LE(500);
+  const createV1 = new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      meta(metadata, true),
+      meta(edition, true),
+      meta(mint.publicKey, true, true),
+      meta(payer.publicKey, false, true), // mint authority
+      meta(payer.publicKey, true, true),
+      meta(payer.publicKey, false, true), // update authority
+      meta(SystemProgram.programId, false),
+      meta(SYSVAR_INSTRUCTIONS_PUBKEY, false),
+      meta(TOKEN_PROGRAM_ID, false),
+    ],
+    data: Buffer.concat([
+      Buffer.from([42, 0]), // Create, V1
+      borshString("Bidx pNFT"),
+      borshString("BPNFT"),
+      borshString("https://example.com/pnft.json"),
+      sellerFeeBps,
+      Buffer.from([
+        0, // creators: None
+        0, // primary_sale_happened
+        1, // is_mutable
+        4, // token_standard: ProgrammableNonFungible
+        0, // collection: None
+        0, // uses: None
+        0, // collection_details: None
+        0, // rule_set: None
+        0, // decimals: None
+        1, 0, // print_supply: Some(Zero)
+      ]),
+    ]),
+  });
+
+  const amount = Buffer.alloc(8);
+  amount.writeBigUInt64LE(BigInt(1));
+  const mintV1 = new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      meta(ownerAccount, true),
+      meta(owner, false),
+      meta(metadata, true),
+      meta(edition, true),
+      meta(tokenRecordPDA(mint.publicKey, ownerAccount), true),
+      meta(mint.publicKey, true),
+      meta(payer.publicKey, false, true),
+      meta(TOKEN_METADATA_PROGRAM_ID, false), // delegate_record: None
+      meta(payer.publicKey, true, true),
+      meta(SystemProgram.programId, false),
+      meta(SYSVAR_INSTRUCTIONS_PUBKEY, false),
+      meta(TOKEN_PROGRAM_ID, false),
+      meta(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+      meta(TOKEN_METADATA_PROGRAM_ID, false), // authorization_rules_program: None
+      meta(TOKEN_METADATA_PROGRAM_ID, false), // authorization_rules: None
+    ],
+    data: Buffer.concat([
+      Buffer.from([43, 0]), // Mint, V1
+      amount,
+      Buffer.from([0]), // authorization_data: None
+    ]),
+  });
+
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(computeBudget, createV1, mintV1),
+    [payer, mint],
+  );
+
+  return { mint: mint.publicKey, ownerAccount };
+}
+
+/**
+ * Remaining accounts bidx takes for a pNFT transfer from `source` to
+ * `destination`, see metaplex::ProgrammableTransfer.
+ */
+function programmableAccounts(
+  mint: PublicKey,
+  source: PublicKey,
+  destination: PublicKey,
+): AccountMeta[] {
+  return [
+    meta(TOKEN_METADATA_PROGRAM_ID, false),
+    meta(metadataPDA(mint), true),
+    meta(editionPDA(mint), false),
+    meta(tokenRecordPDA(mint, source), true),
+    meta(tokenRecordPDA(mint, destination), true),
+    meta(TOKEN_METADATA_PROGRAM_ID, false), // no rule set
+    meta(TOKEN_METADATA_PROGRAM_ID, false),
+    meta(SYSVAR_INSTRUCTIONS_PUBKEY, false),
+    meta(TOKEN_PROGRAM_ID, false),
+    meta(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+  ];
+}
+
+export function runPnftTests(getCtx: () => Ctx) {
+  describe("pnft", () => {
+    before(async function () {
+      const { connection } = getCtx();
+      // Token Metadata only exists on a validator that cloned it
+      if (!(await connection.getAccountInfo(TOKEN_METADATA_PROGRAM_ID))) {
+        this.skip();
+      }
+    });
+
+    it("takes a pNFT into custody and settles it through Token Metadata", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const seller = Keypair.generate();
+      await airdrop(connection, seller.publicKey);
+      const { mint, ownerAccount } = await mintProgrammableNft(
+        connection,
+        platform.admin,
+        seller.publicKey,
+      );
+
+      const [sellerState] = getSellerStatePDA(
+        seller.publicKey,
+        program.programId,
+      );
+      const [auction] = getAuctionPDA(seller.publicKey, 0, program.programId);
+      const [authentication] = getAuthenticationPDA(auction, program.programId);
+      const itemVault = getAssociatedTokenAddressSync(mint, auction, true);
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            seller.publicKey,
+            itemVault,
+            auction,
+            mint,
+          ),
+        ),
+        [seller],
+      );
+
+      const now = Math.floor(Date.now() / 1000);
+      const startDate = new BN(now + TEST_AUCTION_START_DELAY_SECS);
+      const endDate = new BN(now + TEST_AUCTION_DURATION_SECS);
+      await program.methods
+        .createAuction(
+          platform.usdcMint,
+          new BN(1_000_000),
+          new BN(5_000_000),
+          startDate,
+          endDate,
+          null,
+          { digitalNft: {} },
+          { english: {} },
+          auctionTerms(),
+          null,
+        )
+        .accounts({
+          seller: seller.publicKey,
+          sellerState,
+          auction,
+          nftMint: mint,
+          nftMetadata: metadataPDA(mint),
+          sellerNftAccount: ownerAccount,
+          tokenMint: platform.usdcMint,
+          itemVault,
+          authentication,
+          registry: platform.authenticatorsRegistry,
+          platformConfig: platform.platformConfig,
+          lotManifest: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(programmableAccounts(mint, ownerAccount, itemVault))
+        .preInstructions([computeBudget])
+        .signers([seller])
+        .rpc();
+
+      let auctionData = await program.account.auction.fetch(auction);
+      expect(auctionData.itemCustody).to.deep.equal({ programmable: {} });
+      const vault = await getAccount(connection, itemVault);
+      expect(Number(vault.amount)).to.equal(1);
+      expect(vault.isFrozen).to.be.true;
+
+      await waitForUnixTimestamp(startDate.toNumber());
+      await program.methods
+        .activateAuction(new BN(0))
+        .accounts({ seller: seller.publicKey, auction })
+        .rpc();
+
+      const winner = await setupBid(
+        program,
+        connection,
+        platform,
+        auction,
+        new BN(6_000_000),
+      );
+      await endAuction(program, auction, seller.publicKey, 0);
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        seller.publicKey,
+        0,
+      );
+      const winnerNftAccount = getAssociatedTokenAddressSync(
+        mint,
+        winner.bidder.publicKey,
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            winner.bidder.publicKey,
+            winnerNftAccount,
+            winner.bidder.publicKey,
+            mint,
+          ),
+        ),
+        [winner.bidder],
+      );
+
+      const settle = (remainingAccounts: AccountMeta[]) =>
+        program.methods
+          .settleAuction(new BN(0), null)
+          .accounts({
+            winner: winner.bidder.publicKey,
+            seller: seller.publicKey,
+            authenticator: Keypair.generate().publicKey,
+            auction,
+            bid: winner.bid,
+            authentication: null,
+            unitBook: null,
+            lotManifest: null,
+            platformConfig: platform.platformConfig,
+            priceUpdate: null,
+            escrowVault: winner.escrowVault,
+            sellerTokenAccount,
+            treasury: platform.treasuryUsdc,
+            authenticatorTokenAccount: null,
+            nftMint: mint,
+            itemVault,
+            winnerNftAccount,
+            winnerTokenAccount: null,
+            bond: null,
+            bondVault: null,
+            tokenMint: platform.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(remainingAccounts)
+          .preInstructions([computeBudget])
+          .signers([winner.bidder])
+          .rpc();
+
+      // a plain transfer_checked can't move the frozen pNFT
+      await assertAnchorError(settle([]), "ProgrammableAccountsRequired");
+
+      await settle(programmableAccounts(mint, itemVault, winnerNftAccount));
+
+      const winnerNft = await getAccount(connection, winnerNftAccount);
+      expect(Number(winnerNft.amount)).to.equal(1);
+      expect(winnerNft.isFrozen).to.be.true;
+      auctionData = await program.account.auction.fetch(auction);
+      expect(auctionData.auctionStatus).to.deep.equal({ settled: {} });
+    });
+  });
+}
@@ -0,0 +1,367 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  createAssociatedTokenAccountInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  createFundedTokenAccount,
+  endAuction,
+  setupBid,
+  setupCommit,
+  setupDigitalNftAuction,
+  waitForUnixTimestamp,
+  PlatformContext,
+  AuctionContext,
+  CommitContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+const PENALTY_BPS = 1_000; // 10%
+
+export function runSealedBidTests(getCtx: () => Ctx) {
+  describe("sealed bid (commit / reveal)", () => {
+    let auctionCtx: AuctionContext;
+    let revealer: CommitContext;
+    let silent: CommitContext;
+
+    async function reveal(
+      commit: CommitContext,
+      amount: BN,
+      salt: Buffer = commit.salt,
+    ) {
+      const { program, platform } = getCtx();
+      return program.methods
+        .revealBid(new BN(0), amount, Array.from(salt))
+        .accounts({
+          bidder: commit.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: commit.bid,
+          escrowVault: commit.escrowVault,
+          bidderTokenAccount: commit.bidderTokenAccount,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([commit.bidder])
+        .rpc();
+    }
+
+    before(async () => {
+      const { program, connection, platform } = getCtx();
+      auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { sealedBid: { revealWindowSecs: 20, penaltyBps: PENALTY_BPS } },
+      );
+
+      // 6 USDC bid hidden behind an 8 USDC deposit
+      revealer = await setupCommit(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+        new BN(8_000_000),
+      );
+      silent = await setupCommit(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(3_000_000),
+      );
+    });
+
+    it("rejects open bids on a sealed-bid auction", async () => {
+      const { program, connection, platform } = getCtx();
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(2_000_000),
+        ),
+        "SealedBidOnly",
+      );
+    });
+
+    it("end_auction moves a sealed-bid auction into its reveal phase", async () => {
+      const { program } = getCtx();
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ revealing: {} });
+    });
+
+    it("rejects a reveal that does not match the commitment", async () => {
+      await assertAnchorError(
+        reveal(revealer, new BN(7_000_000)),
+        "CommitmentMismatch",
+      );
+    });
+
+    it("valid reveal becomes highest bid and refunds the excess deposit", async () => {
+      const { program, connection } = getCtx();
+      const before = await getAccount(
+        connection,
+        revealer.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+
+      await reveal(revealer, revealer.bidAmount);
+
+      const after = await getAccount(
+        connection,
+        revealer.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(after.amount) - Number(before.amount)).to.equal(2_000_000);
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(6_000_000);
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        revealer.bidder.publicKey.toBase58(),
+      );
+    });
+
+    it("unrevealed commitment forfeits the penalty to the seller on withdraw", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      await waitForUnixTimestamp(
+        auctionData.auctionType.sealedBid.revealEndDate.toNumber(),
+      );
+      await program.methods
+        .endAuction(new BN(0))
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+        })
+        .rpc();
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: silent.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: silent.bid,
+          escrowVault: silent.escrowVault,
+          bidderTokenAccount: silent.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([silent.bidder])
+        .rpc();
+
+      const seller = await getAccount(
+        connection,
+        sellerTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      const expectedPenalty = (3_000_000 * PENALTY_BPS) / 10_000;
+      expect(Number(seller.amount)).to.equal(expectedPenalty);
+    });
+  });
+
+  describe("sealed bid (second price)", () => {
+    it("winner pays the runner-up bid and is refunded the difference", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          sealedBid: {
+            revealWindowSecs: 20,
+            penaltyBps: PENALTY_BPS,
+            pricing: { secondPrice: {} },
+          },
+        },
+      );
+
+      const winner = await setupCommit(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(9_000_000),
+      );
+      const runnerUp = await setupCommit(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(6_000_000),
+      );
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      for (const commit of [winner, runnerUp]) {
+        await program.methods
+          .revealBid(new BN(0), commit.bidAmount, Array.from(commit.salt))
+          .accounts({
+            bidder: commit.bidder.publicKey,
+            seller: auctionCtx.seller.publicKey,
+            auction: auctionCtx.auction,
+            bid: commit.bid,
+            escrowVault: commit.escrowVault,
+            bidderTokenAccount: commit.bidderTokenAccount,
+            tokenMint: platform.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([commit.bidder])
+          .rpc();
+      }
+
+      const revealed = await program.account.auction.fetch(auctionCtx.auction);
+      await waitForUnixTimestamp(
+        revealed.auctionType.sealedBid.revealEndDate.toNumber(),
+      );
+      await program.methods
+        .endAuction(new BN(0))
+        .accounts({
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+        })
+        .rpc();
+
+      const sellerTokenAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        platform.usdcMint,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+      const winnerNftAccount = getAssociatedTokenAddressSync(
+        auctionCtx.nftMint,
+        winner.bidder.publicKey,
+        false,
+        TOKEN_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+      );
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          createAssociatedTokenAccountInstruction(
+            winner.bidder.publicKey,
+            winnerNftAccount,
+            winner.bidder.publicKey,
+            auctionCtx.nftMint,
+            TOKEN_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+        ),
+        [winner.bidder],
+      );
+
+      const before = await getAccount(
+        connection,
+        winner.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+
+      await program.methods
+        .settleAuction(new BN(0), null)
+        .accounts({
+          winner: winner.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          authenticator: Keypair.generate().publicKey,
+          auction: auctionCtx.auction,
+          bid: winner.bid,
+          authentication: null,
+          unitBook: null,
+          lotManifest: null,
+          platformConfig: platform.platformConfig,
+          priceUpdate: null,
+          escrowVault: winner.escrowVault,
+          sellerTokenAccount,
+          treasury: platform.treasuryUsdc,
+          authenticatorTokenAccount: null,
+          nftMint: auctionCtx.nftMint,
+          itemVault: auctionCtx.itemVault,
+          winnerNftAccount,
+          winnerTokenAccount: winner.bidderTokenAccount,
+          bond: null,
+          bondVault: null,
+          tokenMint: platform.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([winner.bidder])
+        .rpc();
+
+      const after = await getAccount(
+        connection,
+        winner.bidderTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(after.amount) - Number(before.amount)).to.equal(3_000_000);
+
+      // seller receives 6 USDC less the 2.5% platform fee
+      const seller = await getAccount(
+        connection,
+        sellerTokenAccount,
+        undefined,
+        TOKEN_PROGRAM_ID,
+      );
+      expect(Number(seller.amount)).to.equal(6_000_000 - 150_000);
+    });
+  });
+}
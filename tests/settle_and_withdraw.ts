@@ -89,7 +89,7 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
       await sendAndConfirmTransaction(connection, setupTx, [winner.bidder]);
 
       await program.methods
-        .settleAuction(new BN(0))
+        .settleAuction(new BN(0), null)
         .accounts({
           winner: winner.bidder.publicKey,
           seller: auctionCtx.seller.publicKey,
@@ -97,7 +97,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
           auction: auctionCtx.auction,
           bid: winner.bid,
           authentication: null,
+          unitBook: null,
+          lotManifest: null,
           platformConfig: platform.platformConfig,
+          priceUpdate: null,
           escrowVault: winner.escrowVault,
           sellerTokenAccount,
           treasury: platform.treasuryUsdc,
@@ -105,6 +108,9 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
           nftMint: auctionCtx.nftMint,
           itemVault: auctionCtx.itemVault,
           winnerNftAccount,
+          winnerTokenAccount: null,
+          bond: null,
+          bondVault: null,
           tokenMint: platform.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -185,7 +191,7 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .settleAuction(new BN(0))
+          .settleAuction(new BN(0), null)
           .accounts({
             winner: winner.bidder.publicKey,
             seller: auctionCtx.seller.publicKey,
@@ -193,7 +199,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             auction: auctionCtx.auction,
             bid: winner.bid,
             authentication: null,
+            unitBook: null,
+            lotManifest: null,
             platformConfig: platform.platformConfig,
+            priceUpdate: null,
             escrowVault: winner.escrowVault,
             sellerTokenAccount,
             treasury: platform.treasuryUsdc,
@@ -201,6 +210,9 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             nftMint: auctionCtx.nftMint,
             itemVault: auctionCtx.itemVault,
             winnerNftAccount,
+            winnerTokenAccount: null,
+            bond: null,
+            bondVault: null,
             tokenMint: platform.usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -272,7 +284,7 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .settleAuction(new BN(0))
+          .settleAuction(new BN(0), null)
           .accounts({
             winner: impostorBid.bidder.publicKey,
             seller: auctionCtx.seller.publicKey,
@@ -280,7 +292,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             auction: auctionCtx.auction,
             bid: impostorBid.bid,
             authentication: null,
+            unitBook: null,
+            lotManifest: null,
             platformConfig: platform.platformConfig,
+            priceUpdate: null,
             escrowVault: impostorBid.escrowVault,
             sellerTokenAccount,
             treasury: platform.treasuryUsdc,
@@ -288,6 +303,9 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             nftMint: auctionCtx.nftMint,
             itemVault: auctionCtx.itemVault,
             winnerNftAccount: impostorNftAccount,
+            winnerTokenAccount: null,
+            bond: null,
+            bondVault: null,
             tokenMint: platform.usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -351,7 +369,7 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
 
       await assertAnchorError(
         program.methods
-          .settleAuction(new BN(0))
+          .settleAuction(new BN(0), null)
           .accounts({
             winner: lowBidder.bidder.publicKey,
             seller: auctionCtx.seller.publicKey,
@@ -359,7 +377,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             auction: auctionCtx.auction,
             bid: lowBidder.bid,
             authentication: null,
+            unitBook: null,
+            lotManifest: null,
             platformConfig: platform.platformConfig,
+            priceUpdate: null,
             escrowVault: lowBidder.escrowVault,
             sellerTokenAccount,
             treasury: platform.treasuryUsdc,
@@ -367,6 +388,9 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             nftMint: auctionCtx.nftMint,
             itemVault: auctionCtx.itemVault,
             winnerNftAccount,
+            winnerTokenAccount: null,
+            bond: null,
+            bondVault: null,
             tokenMint: platform.usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -434,6 +458,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
           bid: loserBid.bid,
           escrowVault: loserBid.escrowVault,
           bidderTokenAccount: loserBid.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
           tokenMint: platform.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
@@ -465,6 +493,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             bid: winnerBid.bid,
             escrowVault: winnerBid.escrowVault,
             bidderTokenAccount: winnerBid.bidderTokenAccount,
+            unitBook: null,
+            sellerTokenAccount: null,
+            bond: null,
+            bondVault: null,
             tokenMint: platform.usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
@@ -503,6 +535,10 @@ export function runSettleAndWithdrawTests(getCtx: () => Ctx) {
             bid: bidder.bid,
             escrowVault: bidder.escrowVault,
             bidderTokenAccount: bidder.bidderTokenAccount,
+            unitBook: null,
+            sellerTokenAccount: null,
+            bond: null,
+            bondVault: null,
             tokenMint: platform.usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
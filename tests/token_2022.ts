@@ -0,0 +1,528 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  ExtensionType,
+  getMintLen,
+  getAssociatedTokenAddressSync,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializePermanentDelegateInstruction,
+  createInitializeTransferHookInstruction,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import { MockTransferHook } from "../target/types/mock_transfer_hook";
+import {
+  airdrop,
+  assertAnchorError,
+  getBidPDA,
+  setupDigitalNftAuction,
+  endAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+const FEE_BPS = 100; // 1%
+
+const hookProgram = () =>
+  anchor.workspace.MockTransferHook as Program<MockTransferHook>;
+
+/**
+ * Creates a Token-2022 mint (6 decimals) with one extra mint extension.
+ */
+async function createToken2022Mint(
+  connection: anchor.web3.Connection,
+  payer: Keypair,
+  extension: ExtensionType,
+): Promise<PublicKey> {
+  const mint = Keypair.generate();
+  const space = getMintLen([extension]);
+  const lamports = await connection.getMinimumBalanceForRentExemption(space);
+
+  let init;
+  switch (extension) {
+    case ExtensionType.TransferFeeConfig:
+      init = createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        payer.publicKey,
+        FEE_BPS,
+        BigInt(1_000_000_000),
+        TOKEN_2022_PROGRAM_ID,
+      );
+      break;
+    case ExtensionType.TransferHook:
+      init = createInitializeTransferHookInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        hookProgram().programId,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      break;
+    default:
+      init = createInitializePermanentDelegateInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        TOKEN_2022_PROGRAM_ID,
+      );
+  }
+
+  await sendAndConfirmTransaction(
+    connection,
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      init,
+      createInitializeMintInstruction(
+        mint.publicKey,
+        6,
+        payer.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID,
+      ),
+    ),
+    [payer, mint],
+  );
+
+  return mint.publicKey;
+}
+
+/**
+ * Registers the mock hook's extra-account-meta list for `mint` and returns
+ * the accounts bidx has to be handed to run the hook: the hook program, its
+ * validation PDA and the per-mint counter that list asks for.
+ */
+async function initializeHook(
+  payer: Keypair,
+  mint: PublicKey,
+): Promise<{ counter: PublicKey; hookAccounts: AccountMeta[] }> {
+  const hook = hookProgram();
+  const [extraAccountMetaList] = PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mint.toBuffer()],
+    hook.programId,
+  );
+  const [counter] = PublicKey.findProgramAddressSync(
+    [Buffer.from("counter"), mint.toBuffer()],
+    hook.programId,
+  );
+
+  await hook.methods
+    .initializeExtraAccountMetaList()
+    .accounts({
+      payer: payer.publicKey,
+      extraAccountMetaList,
+      counter,
+      mint,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([payer])
+    .rpc();
+
+  return {
+    counter,
+    hookAccounts: [
+      { pubkey: hook.programId, isSigner: false, isWritable: false },
+      { pubkey: extraAccountMetaList, isSigner: false, isWritable: false },
+      { pubkey: counter, isSigner: false, isWritable: true },
+    ],
+  };
+}
+
+async function placeToken2022Bid(
+  program: Program<Bidx>,
+  connection: anchor.web3.Connection,
+  platform: PlatformContext,
+  auction: PublicKey,
+  mint: PublicKey,
+  amount: BN,
+  hookAccounts: AccountMeta[] = [],
+) {
+  const bidder = Keypair.generate();
+  await airdrop(connection, bidder.publicKey);
+  const bidderTokenAccount = (
+    await getOrCreateAssociatedTokenAccount(
+      connection,
+      platform.admin,
+      mint,
+      bidder.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+  ).address;
+  await mintTo(
+    connection,
+    platform.admin,
+    mint,
+    bidderTokenAccount,
+    platform.admin,
+    amount.toNumber() * 10,
+    [],
+    undefined,
+    TOKEN_2022_PROGRAM_ID,
+  );
+
+  const [bid] = getBidPDA(bidder.publicKey, auction, program.programId);
+  const escrowVault = getAssociatedTokenAddressSync(
+    mint,
+    bid,
+    true,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  );
+
+  await program.methods
+    .placeBid(amount, [])
+    .accounts({
+      bidder: bidder.publicKey,
+      bid,
+      auction,
+      platformConfig: platform.platformConfig,
+      priceUpdate: null,
+      gateTokenAccount: null,
+      gateMetadata: null,
+      bond: null,
+      bidderTokenAccount,
+      escrowVault,
+      tokenMint: mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(hookAccounts)
+    .signers([bidder])
+    .rpc();
+
+  return { bidder, bid, bidderTokenAccount, escrowVault };
+}
+
+export function runToken2022Tests(getCtx: () => Ctx) {
+  describe("token_2022", () => {
+    it("records what escrow receives net of the transfer fee and refunds it", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const feeMint = await createToken2022Mint(
+        connection,
+        platform.admin,
+        ExtensionType.TransferFeeConfig,
+      );
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { acceptedToken: feeMint },
+      );
+
+      const loser = await placeToken2022Bid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        feeMint,
+        new BN(6_000_000),
+      );
+
+      // 1% of 6_000_000 is withheld on the way into escrow
+      const bidData = await program.account.bid.fetch(loser.bid);
+      expect(bidData.amount.toNumber()).to.equal(5_940_000);
+      const vault = await getAccount(
+        connection,
+        loser.escrowVault,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(Number(vault.amount)).to.equal(5_940_000);
+      let auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBid.toNumber()).to.equal(5_940_000);
+
+      await placeToken2022Bid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        feeMint,
+        new BN(8_000_000),
+      );
+      auctionData = await program.account.auction.fetch(auctionCtx.auction);
+      expect(auctionData.highestBid.toNumber()).to.equal(7_920_000);
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      const before = await getAccount(
+        connection,
+        loser.bidderTokenAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: loser.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: loser.bid,
+          escrowVault: loser.escrowVault,
+          bidderTokenAccount: loser.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: feeMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([loser.bidder])
+        .rpc();
+
+      // the whole escrow goes back, less the fee on the way out
+      const after = await getAccount(
+        connection,
+        loser.bidderTokenAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(Number(after.amount - before.amount)).to.equal(5_940_000 - 59_400);
+    });
+
+    it("forwards transfer-hook accounts into escrow and back out", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const hookMint = await createToken2022Mint(
+        connection,
+        platform.admin,
+        ExtensionType.TransferHook,
+      );
+      const { counter, hookAccounts } = await initializeHook(
+        platform.admin,
+        hookMint,
+      );
+      const transfers = async () =>
+        (
+          await hookProgram().account.transferCounter.fetch(counter)
+        ).transfers.toNumber();
+
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { acceptedToken: hookMint },
+      );
+
+      // without the hook's accounts Token-2022 refuses the transfer
+      let rejected = false;
+      try {
+        await placeToken2022Bid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          hookMint,
+          new BN(6_000_000),
+        );
+      } catch {
+        rejected = true;
+      }
+      expect(rejected).to.be.true;
+
+      const loser = await placeToken2022Bid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        hookMint,
+        new BN(6_000_000),
+        hookAccounts,
+      );
+      expect(await transfers()).to.equal(1);
+      await placeToken2022Bid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        hookMint,
+        new BN(8_000_000),
+        hookAccounts,
+      );
+      expect(await transfers()).to.equal(2);
+
+      await endAuction(
+        program,
+        auctionCtx.auction,
+        auctionCtx.seller.publicKey,
+        0,
+      );
+
+      await program.methods
+        .withdrawBid(new BN(0))
+        .accounts({
+          bidder: loser.bidder.publicKey,
+          seller: auctionCtx.seller.publicKey,
+          auction: auctionCtx.auction,
+          bid: loser.bid,
+          escrowVault: loser.escrowVault,
+          bidderTokenAccount: loser.bidderTokenAccount,
+          unitBook: null,
+          sellerTokenAccount: null,
+          bond: null,
+          bondVault: null,
+          tokenMint: hookMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(hookAccounts)
+        .signers([loser.bidder])
+        .rpc();
+
+      expect(await transfers()).to.equal(3);
+      const refunded = await getAccount(
+        connection,
+        loser.bidderTokenAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      expect(Number(refunded.amount)).to.equal(60_000_000);
+    });
+
+    it("forwards transfer-hook accounts on buy-it-now", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const hookMint = await createToken2022Mint(
+        connection,
+        platform.admin,
+        ExtensionType.TransferHook,
+      );
+      const { counter, hookAccounts } = await initializeHook(
+        platform.admin,
+        hookMint,
+      );
+      const buyNowPrice = new BN(10_000_000);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { acceptedToken: hookMint, buyNowPrice },
+      );
+
+      const buyer = Keypair.generate();
+      await airdrop(connection, buyer.publicKey);
+      const buyerTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          connection,
+          platform.admin,
+          hookMint,
+          buyer.publicKey,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID,
+          ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+      ).address;
+      await mintTo(
+        connection,
+        platform.admin,
+        hookMint,
+        buyerTokenAccount,
+        platform.admin,
+        buyNowPrice.toNumber(),
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+      const [bid] = getBidPDA(
+        buyer.publicKey,
+        auctionCtx.auction,
+        program.programId,
+      );
+
+      await program.methods
+        .buyNow([])
+        .accounts({
+          buyer: buyer.publicKey,
+          bid,
+          auction: auctionCtx.auction,
+          platformConfig: platform.platformConfig,
+          gateTokenAccount: null,
+          gateMetadata: null,
+          bond: null,
+          buyerTokenAccount,
+          escrowVault: getAssociatedTokenAddressSync(
+            hookMint,
+            bid,
+            true,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+          ),
+          tokenMint: hookMint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(hookAccounts)
+        .signers([buyer])
+        .rpc();
+
+      expect(
+        (
+          await hookProgram().account.transferCounter.fetch(counter)
+        ).transfers.toNumber(),
+      ).to.equal(1);
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.auctionStatus).to.deep.equal({ ended: {} });
+    });
+
+    it("rejects a payment mint with an unsupported extension", async () => {
+      const { program, connection, platform } = getCtx();
+
+      const delegateMint = await createToken2022Mint(
+        connection,
+        platform.admin,
+        ExtensionType.PermanentDelegate,
+      );
+
+      await assertAnchorError(
+        setupDigitalNftAuction(program, connection, platform, 0, {
+          acceptedToken: delegateMint,
+        }),
+        "UnsupportedMintExtension",
+      );
+    });
+  });
+}
@@ -0,0 +1,152 @@
+import { Program, BN } from "@coral-xyz/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { Bidx } from "../target/types/bidx";
+import {
+  assertAnchorError,
+  createFundedTokenAccount,
+  createNftMint,
+  setupBid,
+  setupDigitalNftAuction,
+  PlatformContext,
+} from "./helpers";
+
+interface Ctx {
+  program: Program<Bidx>;
+  connection: anchor.web3.Connection;
+  platform: PlatformContext;
+}
+
+export function runTokenGateTests(getCtx: () => Ctx) {
+  describe("token-gated auctions", () => {
+    it("only holders of the gating mint can bid", async () => {
+      const { program, connection, platform } = getCtx();
+      const membershipMint = await createNftMint(connection, platform.admin);
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        { bidderGate: { mint: { mint: membershipMint } } },
+      );
+
+      const holder = Keypair.generate();
+      const holderAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        membershipMint,
+        holder.publicKey,
+        1,
+      );
+      await setupBid(
+        program,
+        connection,
+        platform,
+        auctionCtx.auction,
+        new BN(2_000_000),
+        false,
+        holder,
+        [],
+        { tokenAccount: holderAccount, metadata: null },
+      );
+
+      const auctionData = await program.account.auction.fetch(
+        auctionCtx.auction,
+      );
+      expect(auctionData.highestBidder.toBase58()).to.equal(
+        holder.publicKey.toBase58(),
+      );
+
+      // an empty account of the right mint doesn't count
+      const outsider = Keypair.generate();
+      const emptyAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        membershipMint,
+        outsider.publicKey,
+        0,
+      );
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(3_000_000),
+          false,
+          outsider,
+          [],
+          { tokenAccount: emptyAccount, metadata: null },
+        ),
+        "NotTokenHolder",
+      );
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(3_000_000),
+        ),
+        "GateAccountsRequired",
+      );
+    });
+
+    it("collection gates require the held token's metadata", async () => {
+      const { program, connection, platform } = getCtx();
+      const auctionCtx = await setupDigitalNftAuction(
+        program,
+        connection,
+        platform,
+        0,
+        {
+          bidderGate: {
+            collection: { collection: Keypair.generate().publicKey },
+          },
+        },
+      );
+
+      const holder = Keypair.generate();
+      const nft = await createNftMint(connection, platform.admin);
+      const holderAccount = await createFundedTokenAccount(
+        connection,
+        platform.admin,
+        nft,
+        holder.publicKey,
+        1,
+      );
+
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(2_000_000),
+          false,
+          holder,
+          [],
+          { tokenAccount: holderAccount, metadata: null },
+        ),
+        "GateAccountsRequired",
+      );
+      // any account other than the token's metadata PDA is refused
+      await assertAnchorError(
+        setupBid(
+          program,
+          connection,
+          platform,
+          auctionCtx.auction,
+          new BN(2_000_000),
+          false,
+          holder,
+          [],
+          { tokenAccount: holderAccount, metadata: PublicKey.default },
+        ),
+        "InvalidGateMetadata",
+      );
+    });
+  });
+}